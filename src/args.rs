@@ -0,0 +1,210 @@
+use std::str::FromStr;
+use crate::Error;
+
+/// What the program does, chosen by the first argument
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Play rounds of wordle, the default
+    Play,
+    /// Print every setting and where it came from, `config show`
+    Config,
+    /// Recommend guesses from the colors given by another game
+    Solve,
+    /// Print statistics of the state file
+    Stats,
+    /// Print games saved in the state file
+    Replay,
+    /// Run the recommender against every word in final set
+    Bench,
+    /// Report every problem of two word lists, `lists check FINAL ACC`
+    Lists,
+    /// Host games over TCP, one line for each command
+    Serve,
+    Help,
+    Version,
+}
+
+const COMMANDS: &[(&str, Command, &str)] = &[
+    ("play", Command::Play, "play rounds of wordle (default)"),
+    ("solve", Command::Solve, "read lines of `GUESS COLORS`, recommend the next guesses"),
+    ("stats", Command::Stats, "print statistics of the state file"),
+    ("replay", Command::Replay, "print games saved in the state file"),
+    ("bench", Command::Bench, "run the recommender against every word in final set"),
+    ("config", Command::Config, "`config show` prints every setting and where it came from"),
+    ("lists", Command::Lists, "`lists check FINAL ACC` reports every problem of two word lists"),
+    ("serve", Command::Serve, "host games over TCP for players on the network"),
+];
+
+/// A flag of command line, value is the name of its value, None if it takes no value
+struct FlagSpec {
+    short: Option<&'static str>,
+    long: &'static str,
+    value: Option<&'static str>,
+    help: &'static str,
+}
+
+const fn flag(short: Option<&'static str>, long: &'static str, value: Option<&'static str>, help: &'static str) -> FlagSpec {
+    FlagSpec { short, long, value, help }
+}
+
+const FLAGS: &[FlagSpec] = &[
+    flag(Some("-c"), "--config", Some("FILE"), "load options from a config file"),
+    flag(Some("-f"), "--final-set", Some("FILE"), "words that may be the answer"),
+    flag(Some("-a"), "--acceptable-set", Some("FILE"), "words allowed to guess"),
+    flag(None, "--lang", Some("LANG"), "built-in word lists of a language, like de or fr"),
+    flag(None, "--pattern-cache", Some("FILE"), "cache the pattern table in a file"),
+    flag(Some("-l"), "--length", Some("N"), "length of words"),
+    flag(Some("-m"), "--max-guesses", Some("N"), "number of guesses of a round"),
+    flag(None, "--boards", Some("N"), "guess N words at the same time"),
+    flag(Some("-w"), "--word", Some("WORD"), "play one round with the answer"),
+    flag(Some("-r"), "--random", None, "choose answers at random"),
+    flag(None, "--weighted", None, "choose answers in proportion to their frequency in final set"),
+    flag(Some("-d"), "--day", Some("N"), "start from the answer of day N in random mode"),
+    flag(Some("-s"), "--seed", Some("N"), "seed of random answers"),
+    flag(None, "--daily", None, "play the daily puzzle of today"),
+    flag(None, "--date", Some("YYYY-MM-DD"), "play the daily puzzle of a date"),
+    flag(Some("-D"), "--difficult", None, "hard mode, green and yellow letters must be used"),
+    flag(None, "--strict", None, "hard mode using every deduction of colors"),
+    flag(None, "--adversarial", None, "decide the answer as late as possible"),
+    flag(Some("-t"), "--stats", None, "print statistics after each round"),
+    flag(Some("-S"), "--state", Some("FILE"), "load and save games in a state file"),
+    flag(Some("-h"), "--hint", None, "print possible answers after each guess"),
+    flag(None, "--recommend", None, "recommend guesses with hints"),
+    flag(None, "--solver", Some("NAME"), "strategy of recommend, solve and bench: entropy, minimax, frequency or random"),
+    flag(None, "--share", None, "print an emoji grid after each round"),
+    flag(None, "--protocol", Some("NAME"), "output of play, text or jsonl with a JSON object for each event"),
+    flag(None, "--port", Some("P"), "port of serve, 7878 by default"),
+    flag(None, "--http", None, "serve a JSON API over HTTP instead of lines"),
+    flag(None, "--state-dir", Some("DIR"), "directory of a state file for each player of serve"),
+    flag(None, "--opening", Some("WORD"), "first guess of bench"),
+    flag(None, "--game", Some("N"), "replay the N-th game only"),
+    flag(None, "--last", Some("K"), "replay the last K games only"),
+    flag(None, "--help", None, "print this help"),
+    flag(None, "--version", None, "print the version"),
+];
+
+/// Parsed command line, every flag is kept by its long spelling
+pub struct Args {
+    pub command: Command,
+    /// Arguments of the command that are not flags
+    pub operands: Vec<String>,
+    flags: Vec<(&'static str, Option<String>)>,
+}
+
+impl Args {
+    /// Receive the arguments with the program name first
+    /// Return a result with Error for unknown, duplicate or incomplete flags
+    pub fn parse(args: &[String]) -> Result<Args, Error> {
+        let mut parsed = Args { command: Command::Play, operands: vec![], flags: vec![] };
+        let mut rest = args.iter().skip(1).peekable();
+        if let Some(first) = rest.peek() {
+            if let Some(command) = COMMANDS.iter().find(|x| x.0 == first.as_str()) {
+                parsed.command = command.1;
+                rest.next();
+            }
+        }
+        if parsed.command == Command::Config {
+            match rest.next() {
+                Some(action) if action == "show" => {}
+                Some(action) => return Err(Error::Usage(format!("unknown config action '{}'", action))),
+                None => return Err(Error::Usage("config needs an action, like `config show`".to_string())),
+            }
+        }
+        if parsed.command == Command::Lists {
+            match rest.next() {
+                Some(action) if action == "check" => {}
+                _ => return Err(Error::Usage("lists needs an action, like `lists check FINAL ACC`".to_string())),
+            }
+            while parsed.operands.len() < 2 {
+                match rest.next_if(|x| !x.starts_with('-')) {
+                    Some(path) => parsed.operands.push(path.clone()),
+                    None => return Err(Error::Usage("lists check needs FINAL and ACC".to_string())),
+                }
+            }
+        }
+        while let Some(arg) = rest.next() {
+            //--flag=value is the same as --flag value
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if arg.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let spec = match FLAGS.iter().find(|x| x.long == name || x.short == Some(name)) {
+                Some(spec) => spec,
+                None if name.starts_with('-') => return Err(Error::Usage(format!("unknown flag '{}'", name))),
+                None => return Err(Error::Usage(format!("unexpected argument '{}'", name))),
+            };
+            if parsed.has(spec.long) {
+                return Err(Error::Usage(format!("duplicate flag '{}'", spec.long)));
+            }
+            let value = match (spec.value, inline_value) {
+                (None, None) => None,
+                (None, Some(_)) => return Err(Error::Usage(format!("flag '{}' takes no value", spec.long))),
+                (Some(_), Some(value)) => Some(value),
+                (Some(value_name), None) => match rest.next() {
+                    Some(value) => Some(value.clone()),
+                    None => return Err(Error::Usage(format!("flag '{}' needs a value {}", spec.long, value_name))),
+                },
+            };
+            parsed.flags.push((spec.long, value));
+        }
+        if parsed.has("--help") {
+            parsed.command = Command::Help;
+        } else if parsed.has("--version") {
+            parsed.command = Command::Version;
+        }
+        Ok(parsed)
+    }
+
+    /// Long spellings of the flags given
+    pub fn flags(&self) -> impl Iterator<Item = &str> {
+        self.flags.iter().map(|x| x.0)
+    }
+
+    /// Whether the flag of the long spelling is given
+    pub fn has(&self, long: &str) -> bool {
+        self.flags.iter().any(|x| x.0 == long)
+    }
+
+    /// Value of the flag of the long spelling
+    pub fn value(&self, long: &str) -> Option<&String> {
+        self.flags.iter().find(|x| x.0 == long).and_then(|x| x.1.as_ref())
+    }
+
+    /// Value of the flag converted to a number or other type
+    /// Return a result with Error naming the flag if the value cannot be converted
+    pub fn parse_value<T: FromStr>(&self, long: &str) -> Result<Option<T>, Error> {
+        match self.value(long) {
+            None => Ok(None),
+            Some(value) => match value.parse() {
+                Ok(x) => Ok(Some(x)),
+                Err(_) => Err(Error::Usage(format!("invalid value '{}' for '{}'", value, long))),
+            },
+        }
+    }
+}
+
+/// Usage, subcommands and every flag
+pub fn help() -> String {
+    let mut help = String::from("Usage: wordle [COMMAND] [OPTIONS]\n\nCommands:\n");
+    for (name, _, text) in COMMANDS {
+        help += &format!("  {:<10} {}\n", name, text);
+    }
+    help += "\nOptions:\n";
+    for spec in FLAGS {
+        let spelling = match spec.short {
+            Some(short) => format!("{}, {}", short, spec.long),
+            None => format!("    {}", spec.long),
+        };
+        let spelling = match spec.value {
+            Some(value) => format!("{} {}", spelling, value),
+            None => spelling,
+        };
+        help += &format!("  {:<32} {}\n", spelling, spec.help);
+    }
+    help.pop();
+    help
+}
+
+pub fn version() -> String {
+    format!("wordle {}", env!("CARGO_PKG_VERSION"))
+}
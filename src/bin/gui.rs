@@ -4,16 +4,8 @@ use fltk::{app, button::{self, Button}, dialog, enums::{Color, Font, FrameType},
            group::{self, PackType}, menu, prelude::*, window::{Window}};
 use fltk::enums::Shortcut;
 use func;
-use func::{update_round_alphabet_color, Info, RoundInfo, stats_to_string};
+use func::{config, GameSession, Info, Statistics, stats_to_string};
 
-pub const ALPHABET: &[char] = &[
-    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
-    't', 'u', 'v', 'w', 'x', 'y', 'z',
-];
-pub const KEYBOARD_ALPHABET: &[char] = &[
-    'q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p', 'a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l',
-    'z', 'x', 'c', 'v', 'b', 'n', 'm',
-];
 pub const GREY: u32 = 0xd1d1d1;
 pub const RED: u32 = 0x787c7f;
 pub const GREEN: u32 = 0x6ca965;
@@ -31,6 +23,15 @@ enum Message {
     Show
 }
 
+/// Replace the value of a flag in args, or add the flag, so args can be analyzed again
+fn set_arg(args: &mut Vec<String>, spellings: &[&str], value: String) {
+    if let Some(i) = args.iter().position(|x| spellings.contains(&x.as_str())) {
+        args.drain(i..(i + 2).min(args.len()));
+    }
+    args.push(spellings[spellings.len() - 1].to_string());
+    args.push(value);
+}
+
 fn main() {
     let app = app::App::default();
     app::set_visible_focus(false);
@@ -51,7 +52,6 @@ fn main() {
     btn_enter.set_color(Color::Light2);
     let mut btn_undo = button::Button::new(510, 610, 70, 50, "@undo");
     btn_undo.set_color(Color::Light2);
-    let mut char_num = 0;
 
     //create menu
     let mut menubar = menu::MenuBar::new(0, 0, width, 25, "rew");
@@ -95,16 +95,30 @@ fn main() {
 
 
 
+    //analyze args first, frames depend on word length
+    let mut word_to_guess = String::new();
+    let mut info = Info::new();
+    let mut args: Vec<String> = std::env::args().collect();
+    let configs = config::discover();
+    if let Err(err) = func::info_analyze(&mut word_to_guess, &mut info, &args, &configs) {
+        dialog::message(500, 300, &err.to_string());
+        return;
+    }
+    let word_length = info.word_length;
+    let max_guesses = info.max_guesses;
+    let frame_size = std::cmp::min(
+        75, std::cmp::min((width - 40) / word_length as i32, 450 / max_guesses as i32));
+
     // create show frame
     let mut frame_list: Vec<Frame> = vec![];
-    for i in 0..6 {
+    for i in 0..max_guesses as i32 {
         let mut frame_pack1 = group::Pack::default_fill()
             .with_type(PackType::Horizontal)
-            .with_pos(130, 40 + i * 75)
-            .with_size(1000, 75);
+            .with_pos((width - frame_size * word_length as i32) / 2, 40 + i * frame_size)
+            .with_size(1000, frame_size);
         frame_pack1.auto_layout();
-        for _j in 0..5 {
-            let mut temp = Frame::default().with_size(75, 75);
+        for _j in 0..word_length {
+            let mut temp = Frame::default().with_size(frame_size, frame_size);
             temp.set_frame(FrameType::DownBox);
             temp.set_color(Color::Light3);
             frame_list.push(temp)
@@ -112,54 +126,25 @@ fn main() {
         frame_pack1.end();
     }
 
-    //create letter button
+    //create letter buttons by rows of the keyboard, the last row ends before the undo button
     let mut letter_btn: Vec<Button> = vec![];
-
-    let mut letter_pack1 = group::Pack::default_fill()
-        .with_type(PackType::Horizontal)
-        .with_pos(50, 500)
-        .with_size(1000, 50);
-    letter_pack1.auto_layout();
-    letter_pack1.set_spacing(5);
-    for _i in 0..10 {
-        letter_btn.push(
-            Button::default()
-                .with_size(50, 50)
-                .with_label(&KEYBOARD_ALPHABET[char_num].to_string().to_ascii_uppercase()),
-        );
-        char_num += 1;
-    }
-    letter_pack1.end();
-
-    let mut letter_pack2 = group::Pack::default_fill()
-        .with_type(PackType::Horizontal)
-        .with_pos(80, 555)
-        .with_size(1000, 50);
-    letter_pack2.set_spacing(5);
-    for _i in 0..9 {
-        letter_btn.push(
-            Button::default()
-                .with_size(50, 50)
-                .with_label(&KEYBOARD_ALPHABET[char_num].to_string().to_ascii_uppercase()),
-        );
-        char_num += 1;
-    }
-    letter_pack2.end();
-
-    let mut letter_pack3 = group::Pack::default_fill()
-        .with_type(PackType::Horizontal)
-        .with_pos(125, 610)
-        .with_size(1000, 50);
-    letter_pack3.set_spacing(5);
-    for _i in 0..7 {
-        letter_btn.push(
-            Button::default()
-                .with_size(50, 50)
-                .with_label(&KEYBOARD_ALPHABET[char_num].to_string().to_ascii_uppercase()),
-        );
-        char_num += 1;
+    let row_positions = [(50, 500, width - 10), (80, 555, width - 10), (125, 610, 505)];
+    for (row, (x, y, row_end)) in info.keyboard.iter().zip(row_positions) {
+        let key_size = std::cmp::min(50, (row_end - x + 5) / row.chars().count() as i32 - 5);
+        let mut letter_pack = group::Pack::default_fill()
+            .with_type(PackType::Horizontal)
+            .with_pos(x, y)
+            .with_size(1000, 50);
+        letter_pack.set_spacing(5);
+        for letter in row.chars() {
+            letter_btn.push(
+                Button::default()
+                    .with_size(key_size, 50)
+                    .with_label(&func::to_upper_word(&letter.to_string())),
+            );
+        }
+        letter_pack.end();
     }
-    letter_pack3.end();
     //set button style
     for i in &mut letter_btn {
         i.set_color(Color::Light3);
@@ -167,15 +152,10 @@ fn main() {
     }
 
     //start game
-    let mut word_to_guess = String::new();
-    let mut info = Info::new();
-    let mut round_info = RoundInfo::new(&info);
     let mut guess_word = String::new();
-    let mut guess_count: usize = 0;
     let mut is_good: bool = true;// a condition variable controlled by return
-    let mut args: Vec<String> = vec![];
-    func::get_word_by_start_day(&mut word_to_guess, &info, 0);
-    let mut is_success = false;
+    word_to_guess = info.next_random_answer();
+    let mut session = GameSession::new(&info, &word_to_guess);
     for but in &mut letter_btn {
         but.emit(s, Message::Letter(but.label().chars().next().unwrap()))
     }
@@ -189,11 +169,11 @@ fn main() {
             match val {
                 Message::Letter(ch) => {
                     if is_good {
-                        if guess_word.len() < 5 {
+                        if guess_word.chars().count() < word_length {
                             println!("ch");
-                            let position = guess_count * 5 + guess_word.len();
+                            let position = session.history().len() * word_length + guess_word.chars().count();
                             frame_list[position].set_label(&ch.to_string());
-                            guess_word.push(ch.to_ascii_lowercase());
+                            guess_word.extend(ch.to_lowercase());
                         } else {
                             dialog::message(500, 300, "Word already full!");
                         }
@@ -205,44 +185,42 @@ fn main() {
                     println!("enter");
 
                     if is_good {
-                        if info.acceptable_set.contains(&guess_word) {
-                            let result = func::calculate_color(&word_to_guess, &guess_word);
-                            is_success = true;
-                            update_round_alphabet_color(&mut round_info, &guess_word, &result);
-                            for i in 0..5 {
-                                let color = result[i].to_hex();
-                                frame_list[guess_count * 5 + i].set_color(Color::from_hex(color));
-                                //judge if succeed
-                                if let func::Color::G = result[i] {} else {
-                                    is_success = false;
+                        match session.submit_guess(&guess_word) {
+                            Ok(outcome) => {
+                                let guess_count = session.history().len() - 1;
+                                for i in 0..word_length {
+                                    let color = outcome.colors[i].to_hex();
+                                    frame_list[guess_count * word_length + i].set_color(Color::from_hex(color));
                                 }
-                            }
-                            for i in 0..26 {
-                                for num_in_alpha in 0..26 {
-                                    if letter_btn[i].label().chars().next().unwrap().to_ascii_lowercase()
-                                        == ALPHABET[num_in_alpha]
-                                    {
-                                        let color = round_info.alphabet_color[num_in_alpha].to_hex();
-                                        letter_btn[i].set_color(Color::from_hex(color));
+                                for i in 0..letter_btn.len() {
+                                    for num_in_alpha in 0..session.alphabet().len() {
+                                        if letter_btn[i].label().to_lowercase().chars().next()
+                                            == Some(session.alphabet()[num_in_alpha])
+                                        {
+                                            let color = session.keyboard()[num_in_alpha].to_hex();
+                                            letter_btn[i].set_color(Color::from_hex(color));
+                                        }
+                                    }
+                                }
+                                app.redraw();
+                                //new guess
+                                guess_word.clear();
+                                if session.is_over() {
+                                    info.record_session(&session);
+                                    is_good = false;
+                                    if info.is_share {
+                                        if let Some(game) = info.state.games.last() {
+                                            app::copy(&func::share_to_string(game, &info));
+                                        }
+                                    }
+                                    if outcome.is_correct {
+                                        dialog::message(500, 300, "You win! Click return arrow to reset ");
+                                    } else {
+                                        dialog::message(500, 300, "You failed.Click return arrow to reset.");
                                     }
                                 }
                             }
-                            round_info.word_guessed_this_round.push(guess_word.clone().to_ascii_uppercase());
-                            app.redraw();
-                            //new guess
-                            guess_word.clear();
-                            guess_count += 1;
-                            if guess_count == 6 {
-                                dialog::message(500, 300, "You failed.Click return arrow to reset.");
-                                is_good = false;
-                            }
-                        } else {
-                            dialog::message(500, 300, "Word doesn't exist!");
-                        }
-                        if is_success {
-                            info.state.games.push(func::Game { answer: word_to_guess.clone(), guesses: round_info.word_guessed_this_round.clone() });
-                            dialog::message(500, 300, "You win! Click return arrow to reset ");
-                            is_good = false;
+                            Err(_) => dialog::message(500, 300, "Word doesn't exist!"),
                         }
                     } else {
                         let choices = dialog::choice2(
@@ -253,8 +231,6 @@ fn main() {
                                 match choice {
                                     0 => {
                                         is_good = true;
-                                        is_success = false;
-                                        round_info = RoundInfo::new(&info);
                                         for frame in &mut frame_list {
                                             frame.set_color(Color::from_hex(GREY));
                                             frame.set_label("")
@@ -263,16 +239,9 @@ fn main() {
                                             btn.set_color(Color::from_hex(GREY));
                                             btn.redraw();
                                         }
-                                        //update round info
-                                        info.state.games.push(func::Game {
-                                            answer: word_to_guess.clone().to_ascii_uppercase(),
-                                            guesses: round_info.word_guessed_this_round.clone(),
-                                        });
-                                        info.state.total_rounds += 1;
-                                        guess_count = 0;
-                                        info.day += 1;
-                                        word_to_guess =
-                                            info.final_set[info.shuffled_seq[info.day as usize]].clone();
+                                        guess_word.clear();
+                                        word_to_guess = info.next_random_answer();
+                                        session = GameSession::new(&info, &word_to_guess);
                                     }
                                     1 => {
                                         is_good = false;
@@ -288,7 +257,7 @@ fn main() {
                     if is_good {
                         match guess_word.pop() {
                             Some(_) => {
-                                frame_list[guess_count * 5 + guess_word.len()].set_label("");
+                                frame_list[session.history().len() * word_length + guess_word.chars().count()].set_label("");
                             }
                             None => dialog::message(500, 300, "Word already empty!"),
                         }
@@ -299,13 +268,12 @@ fn main() {
                 Message::Seed => {
                     let seed = dialog::input(
                         500, 300, "Start game with a random seed:", "");
-                    args.push("--seed".to_string());
                     if let Some(s) = seed {
-                        args.push(s);
-                        match func::info_analyze(&mut word_to_guess, &mut info, &args) {
+                        set_arg(&mut args, &["-s", "--seed"], s);
+                        match func::info_analyze(&mut word_to_guess, &mut info, &args, &configs) {
                             Ok(_) => {
-                                round_info = RoundInfo::new(&info);
-                                func::get_word_by_start_day(&mut word_to_guess, &info, 0);
+                                word_to_guess = info.next_random_answer();
+                                session = GameSession::new(&info, &word_to_guess);
                             }
                             Err(err) => {
                                 dialog::message(500, 300, &err.to_string());
@@ -319,16 +287,19 @@ fn main() {
                     saving.show();
                     info.state_path = saving.filename().into_os_string().into_string().unwrap();
                     let state_string = serde_json::to_string_pretty(&info.state).unwrap();
-                    fs::write(saving.filename(), state_string).unwrap();
+                    if let Err(err) = fs::write(saving.filename(), state_string) {
+                        dialog::message(500, 300, &err.to_string());
+                    }
                 }
                 Message::Open => {
                     let mut saving = dialog::NativeFileChooser::new(
                         dialog::NativeFileChooserType::BrowseFile);
                     saving.show();
                     let state_path = saving.filename().into_os_string().into_string().unwrap();
-                    args.push("-S".to_string());
-                    args.push(state_path);
-                    func::info_analyze(&mut word_to_guess, &mut info, &args).expect("input error");
+                    set_arg(&mut args, &["-S", "--state"], state_path);
+                    if let Err(err) = func::info_analyze(&mut word_to_guess, &mut info, &args, &configs) {
+                        dialog::message(500, 300, &err.to_string());
+                    }
                 }
                 Message::Quit => {
                     if info.state_path.is_empty() {
@@ -343,7 +314,8 @@ fn main() {
                 }
                 Message::Show => {
                     println!("{}",stats_to_string(&mut info));
-                    dialog::message(500, 300,&stats_to_string(&mut info))
+                    let statistics = Statistics::from_games(&info.state.games, info.max_guesses);
+                    dialog::message(500, 300, &format!("{}\n{}", statistics, stats_to_string(&mut info)))
                 }
             }
         }
@@ -0,0 +1,143 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::Error;
+
+/// Name of the project config, searched from the current directory up
+pub const PROJECT_CONFIG: &str = ".wordle.toml";
+
+/// Every key of a config file, in the order `config show` prints them
+pub const KEYS: &[&str] = &[
+    "length", "max_guesses", "boards", "random", "weighted", "difficult", "strict", "adversarial", "stats", "share",
+    "day", "seed", "epoch", "lang", "solver", "final_set", "acceptable_set", "state", "pattern_cache", "word",
+];
+
+/// Keys a request to the HTTP server may set for its game, others could read files of the server
+pub const REQUEST_KEYS: &[&str] = &["seed", "day", "difficult", "strict", "max_guesses", "lang", "weighted"];
+
+/// Where the value of a setting came from, later ones override earlier ones
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigSource {
+    Default,
+    User(String),
+    Project(String),
+    /// The file given by --config
+    File(String),
+    /// Options in the body of a request to the HTTP server
+    Request,
+    CommandLine,
+}
+
+impl ConfigSource {
+    /// Path of the config file, None for default values and flags
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            ConfigSource::User(path) | ConfigSource::Project(path) | ConfigSource::File(path) => Some(path),
+            ConfigSource::Default | ConfigSource::Request | ConfigSource::CommandLine => None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::User(path) => write!(f, "user config {}", path),
+            ConfigSource::Project(path) => write!(f, "project config {}", path),
+            ConfigSource::File(path) => write!(f, "--config {}", path),
+            ConfigSource::Request => write!(f, "request"),
+            ConfigSource::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// The config key set by a flag of the long spelling
+pub fn flag_key(long: &str) -> Option<&'static str> {
+    match long {
+        "--length" => Some("length"),
+        "--max-guesses" => Some("max_guesses"),
+        "--boards" => Some("boards"),
+        "--random" => Some("random"),
+        "--weighted" => Some("weighted"),
+        "--difficult" => Some("difficult"),
+        "--strict" => Some("strict"),
+        "--adversarial" => Some("adversarial"),
+        "--stats" => Some("stats"),
+        "--share" => Some("share"),
+        "--day" => Some("day"),
+        "--seed" => Some("seed"),
+        "--lang" => Some("lang"),
+        "--solver" => Some("solver"),
+        "--final-set" => Some("final_set"),
+        "--acceptable-set" => Some("acceptable_set"),
+        "--state" => Some("state"),
+        "--pattern-cache" => Some("pattern_cache"),
+        "--word" => Some("word"),
+        _ => None,
+    }
+}
+
+/// User config in $XDG_CONFIG_HOME/wordle/, or ~/.config/wordle/ if it is not set
+fn user_config() -> Option<PathBuf> {
+    let config_home = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    ["config.toml", "config.json"].iter()
+        .map(|name| config_home.join("wordle").join(name))
+        .find(|path| path.is_file())
+}
+
+/// Nearest .wordle.toml in the current directory or its parents
+fn project_config() -> Option<PathBuf> {
+    let current = std::env::current_dir().ok()?;
+    current.ancestors().map(|dir| dir.join(PROJECT_CONFIG)).find(|path| path.is_file())
+}
+
+/// Config files found without --config, from lower to higher precedence
+pub fn discover() -> Vec<ConfigSource> {
+    let mut sources = vec![];
+    if let Some(path) = user_config() {
+        sources.push(ConfigSource::User(path.to_string_lossy().to_string()));
+    }
+    if let Some(path) = project_config() {
+        sources.push(ConfigSource::Project(path.to_string_lossy().to_string()));
+    }
+    sources
+}
+
+/// Receive the path of a config file, TOML if it ends with .toml, otherwise JSON
+/// Return the table of settings, or Error if it cannot be read or parsed
+pub fn read_config(path: &str) -> Result<serde_json::Value, Error> {
+    let config_string = fs::read_to_string(path)
+        .map_err(|source| Error::Io { path: path.to_string(), source })?;
+    let config: serde_json::Value = if Path::new(path).extension().is_some_and(|x| x == "toml") {
+        toml::from_str(&config_string).map_err(|err| Error::Config { path: path.to_string(), message: err.to_string() })?
+    } else {
+        serde_json::from_str(&config_string).map_err(|err| Error::Config { path: path.to_string(), message: err.to_string() })?
+    };
+    match config.as_object() {
+        None => Err(Error::Config { path: path.to_string(), message: "should be a table of settings".to_string() }),
+        Some(table) => match table.keys().find(|key| !KEYS.contains(&key.as_str())) {
+            Some(key) => Err(Error::Config { path: path.to_string(), message: format!("unknown key '{}'", key) }),
+            None => Ok(config),
+        },
+    }
+}
+
+/// Receive the body of a request to the HTTP server, an empty body has no options
+/// Return the table of options, or Error if it is not JSON or has a key not in REQUEST_KEYS
+pub fn read_request(body: &str) -> Result<serde_json::Value, Error> {
+    let wrong = |message: String| Error::Config { path: ConfigSource::Request.to_string(), message };
+    if body.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    let options: serde_json::Value = serde_json::from_str(body).map_err(|err| wrong(err.to_string()))?;
+    match options.as_object() {
+        None => Err(wrong("should be a table of options".to_string())),
+        Some(table) => match table.keys().find(|key| !REQUEST_KEYS.contains(&key.as_str())) {
+            Some(key) => Err(wrong(format!("key '{}' cannot be set by a request", key))),
+            None => Ok(options),
+        },
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::Error;
+
+/// Day of the first daily puzzle if not set in config
+pub const DEFAULT_EPOCH: &str = "2021-06-19";
+
+/// Receive a date like 2022-09-09, return the number of days since 1970-01-01
+/// Return a result with Error if the date is invalid
+pub fn parse_date(date: &str) -> Result<i64, Error> {
+    let invalid = || Error::Parse { value: date.to_string(), expected: "a date like 2022-09-09" };
+    let parts: Vec<&str> = date.split('-').collect();
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+        return Err(invalid());
+    }
+    let year: i64 = parts[0].parse().map_err(|_| invalid())?;
+    let month: i64 = parts[1].parse().map_err(|_| invalid())?;
+    let day: i64 = parts[2].parse().map_err(|_| invalid())?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return Err(invalid());
+    }
+    Ok(days_from_civil(year, month, day))
+}
+
+/// Receive the number of days since 1970-01-01, return the date like 2022-09-09
+pub fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Seconds since 1970-01-01, SOURCE_DATE_EPOCH overrides the clock for reproducible output
+pub fn timestamp() -> u64 {
+    if let Some(seconds) = std::env::var("SOURCE_DATE_EPOCH").ok().and_then(|x| x.parse().ok()) {
+        return seconds;
+    }
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|x| x.as_secs()).unwrap_or(0)
+}
+
+/// Today in UTC, so everyone gets the same puzzle at the same time
+pub fn today() -> String {
+    format_date((timestamp() / 86400) as i64)
+}
+
+/// Receive the date of puzzle and the epoch, return the number of the puzzle, starting from 0
+/// Return a result with Error if a date is invalid or the puzzle is before epoch
+pub fn puzzle_number(date: &str, epoch: &str) -> Result<usize, Error> {
+    let days = parse_date(date)? - parse_date(epoch)?;
+    if days < 0 {
+        return Err(Error::InvalidArgs(format!("there is no puzzle before {}", epoch)));
+    }
+    Ok(days as usize)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 => if is_leap_year(year) { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar
+/// Algorithm from http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
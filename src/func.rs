@@ -1,14 +1,37 @@
+pub mod args;
 mod builtin_words;
+pub mod config;
+pub mod lang;
+pub mod lists;
+pub mod daily;
+pub mod http;
+pub mod pattern;
+pub mod server;
+pub mod solver;
 
 use std::collections::HashMap;
 use rand::prelude::{SliceRandom, StdRng};
-use rand::SeedableRng;
-use std::{fs, io};
+use rand::{Rng, SeedableRng};
+use std::fs;
 use std::cmp::Ordering;
+use std::ops::Range;
+use std::fmt;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use builtin_words::{ACCEPTABLE, FINAL};
+use pattern::PatternTable;
+use args::{Args, Command};
+use config::ConfigSource;
 
 pub const WORDLE_LENS: usize = 5;
+pub const MIN_WORDLE_LENS: usize = 4;
+pub const MAX_WORDLE_LENS: usize = 11;
+pub const MAX_GUESSES: usize = 6;
+pub const DEFAULT_PORT: u16 = 7878;
+/// Version of the state file written by this program
+/// Version 1 has only total_rounds and the answer and guesses of each game
+pub const STATE_VERSION: u32 = 2;
+/// Letters of the built-in lists, and of any lists using only ASCII letters
 pub const ALPHABET: &[char] = &['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n',
     'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z'];
 pub const GREY: u32 = 0xd1d1d1;
@@ -16,47 +39,188 @@ pub const RED: u32 = 0x787c7f;
 pub const GREEN: u32 = 0x6ca965;
 pub const YELLOW: u32 = 0xc8b653;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Game {
+    /// Answer of the game, or of the first board in multi-board mode
     pub answer: String,
+    /// Answers of every board, only in multi-board mode
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub answers: Vec<String>,
     pub guesses: Vec<String>,
+    #[serde(default = "default_max_guesses", skip_serializing_if = "is_default_max_guesses")]
+    pub max_guesses: usize,
+    /// Date of the daily puzzle, only in daily mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Seconds since 1970-01-01 when the game finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<GameMode>,
+    /// "builtin" or "builtin-LANG", or the fingerprint of the word lists given by -f and -a
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word_list: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<Outcome>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Options a game was played with, seed and day only in random mode
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GameMode {
+    pub difficult: bool,
+    pub random: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day: Option<i32>,
+    /// Answers were chosen in proportion to their frequency
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub weighted: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Win,
+    Loss,
+}
+
+impl Game {
+    /// A game succeeds when every answer is guessed
+    pub fn is_success(&self) -> bool {
+        if self.answers.is_empty() {
+            self.guesses.contains(&self.answer)
+        } else {
+            self.answers.iter().all(|answer| self.guesses.contains(answer))
+        }
+    }
+}
+
+fn default_max_guesses() -> usize {
+    MAX_GUESSES
+}
+
+fn is_default_max_guesses(max_guesses: &usize) -> bool {
+    *max_guesses == MAX_GUESSES
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct State {
+    /// Missing in state files of version 1
+    #[serde(default = "legacy_state_version")]
+    pub version: u32,
     pub total_rounds: i32,
     pub games: Vec<Game>,
 }
 
+fn legacy_state_version() -> u32 {
+    1
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::new()
+    }
+}
+
+impl State {
+    pub fn new() -> State {
+        State {
+            version: STATE_VERSION,
+            total_rounds: 0,
+            games: vec![],
+        }
+    }
+
+    /// Upgrade a state loaded from an older version, the outcome of old games is recovered from guesses
+    /// Other metadata of old games is unknown and stays empty
+    pub fn migrate(&mut self) {
+        if self.version < 2 {
+            for game in &mut self.games {
+                if game.outcome.is_none() {
+                    game.outcome = Some(if game.is_success() { Outcome::Win } else { Outcome::Loss });
+                }
+            }
+        }
+        self.version = STATE_VERSION;
+    }
+}
+
+#[derive(Clone)]
 pub struct Info {
-    is_difficult: bool,
+    pub is_difficult: bool,
+    pub is_strict: bool,
+    pub is_adversarial: bool,
     pub is_random: bool,
+    pub is_weighted: bool,
     pub is_word_specified: bool,
     pub is_stats: bool,
-    is_recommend: bool,
+    pub is_recommend: bool,
     is_seeded: bool,
     is_special_day: bool,
     pub is_stated: bool,
-    is_hint: bool,
+    pub is_hint: bool,
+    pub command: Command,
+    pub is_share: bool,
+    /// Number of the game to replay, starting from 1, 0 for not specified
+    replay_game: usize,
+    /// Number of last games to replay, 0 for not specified
+    replay_last: usize,
     succeeded_game: i32,
     failed_game: i32,
     words_appeared: Vec<String>,
     pub day: i32,
     pub seed: u64,
+    pub word_length: usize,
+    /// Code of the language pack of built-in lists
+    pub lang: String,
+    /// Letters used by the word lists, in order of the keyboard
+    pub alphabet: Vec<char>,
+    /// Rows of the keyboard, from the language pack if it has every letter of alphabet
+    pub keyboard: Vec<String>,
+    pub max_guesses: usize,
+    pub boards: usize,
+    /// Date of the daily puzzle, empty if not in daily mode
+    pub date: String,
+    epoch: String,
+    pub opening: String,
+    /// Name of the solver of recommend, solve and bench
+    pub solver: String,
     pub shuffled_seq: Vec<usize>,
+    /// Frequency of each word in final set, 1 for built-in lists
+    final_weights: Vec<u64>,
     final_path: String,
     acceptable_path: String,
     pub final_set: Vec<String>,
-    pub acceptable_set: Vec<String>,
+    /// Shared with every GameSession, so a round does not copy the whole list
+    pub acceptable_set: Arc<Vec<String>>,
     pub state: State,
     pub state_path: String,
+    /// Port of `serve`, and the directory of a state file for each player
+    pub port: u16,
+    /// `serve` answers a JSON API over HTTP instead of lines
+    pub is_http: bool,
+    pub state_dir: String,
+    /// Print every event of play as a line of JSON, `--protocol jsonl`
+    pub is_jsonl: bool,
+    pattern_cache_path: String,
+    /// Where each setting came from, missing for default values
+    sources: HashMap<String, ConfigSource>,
+    pub pattern_table: Option<Arc<PatternTable>>,
 }
 
 impl Info {
     pub fn new() -> Info {
         Info {
             is_random: false,
+            is_weighted: false,
             is_difficult: false,
+            is_strict: false,
+            is_adversarial: false,
             is_word_specified: false,
             is_stats: false,
             is_recommend: false,
@@ -64,17 +228,32 @@ impl Info {
             is_special_day: false,
             is_stated: false,
             is_hint: false,
+            command: Command::Play,
+            is_share: false,
+            replay_game: 0,
+            replay_last: 0,
             succeeded_game: 0,
             failed_game: 0,
             words_appeared: vec![],
             day: 1,
             seed: 0,
+            word_length: WORDLE_LENS,
+            lang: lang::DEFAULT_LANG.to_string(),
+            alphabet: ALPHABET.to_vec(),
+            keyboard: vec![],
+            max_guesses: MAX_GUESSES,
+            boards: 1,
+            date: String::new(),
+            epoch: daily::DEFAULT_EPOCH.to_string(),
+            opening: String::new(),
+            solver: solver::DEFAULT_SOLVER.to_string(),
             shuffled_seq: {
                 let mut temp: Vec<usize> = (0..FINAL.len()).collect();
                 let mut rng: StdRng = SeedableRng::seed_from_u64(0);
                 temp.shuffle(&mut rng);
                 temp
             },
+            final_weights: vec![1; FINAL.len()],
             final_path: "".to_string(),
             acceptable_path: "".to_string(),
             final_set: {
@@ -84,64 +263,288 @@ impl Info {
                 };
                 a
             },
-            acceptable_set: {
+            acceptable_set: Arc::new({
                 let mut a: Vec<String> = vec![];
                 for i in ACCEPTABLE {
                     a.push(i.to_string());
                 };
                 a
-            },
-            state: {
-                State {
-                    total_rounds: 0,
-                    games: vec![],
-                }
-            },
+            }),
+            state: State::new(),
             state_path: String::new(),
+            port: DEFAULT_PORT,
+            is_http: false,
+            state_dir: "states".to_string(),
+            is_jsonl: false,
+            pattern_cache_path: String::new(),
+            sources: HashMap::new(),
+            pattern_table: None,
         }
     }
-    fn load_config(&mut self, word_to_guess: &mut String, config: &serde_json::Value) {
+    /// Receive the parsed config file and its path, values in it override values loaded before
+    /// Return a result with Error naming the key of a value of wrong type
+    fn load_config(&mut self, word_to_guess: &mut String, config: &serde_json::Value, source: &ConfigSource) -> Result<(), Error> {
+        let path = source.path().map(String::from).unwrap_or_else(|| source.to_string());
+        let wrong = |key: &str, expected: &str| Error::Config {
+            path: path.to_string(),
+            message: format!("'{}' should be {}", key, expected),
+        };
+        if let Some(table) = config.as_object() {
+            for key in table.keys() {
+                self.sources.insert(key.clone(), source.clone());
+            }
+        }
+        if let Some(length) = config.get("length") {
+            self.word_length = length.as_u64().ok_or_else(|| wrong("length", "a number"))? as usize;
+        }
+        if let Some(max_guesses) = config.get("max_guesses") {
+            self.max_guesses = max_guesses.as_u64().ok_or_else(|| wrong("max_guesses", "a number"))? as usize;
+        }
+        if let Some(lang) = config.get("lang") {
+            self.lang = lang.as_str().ok_or_else(|| wrong("lang", "a string"))?.to_string();
+        }
+        if let Some(solver) = config.get("solver") {
+            self.solver = solver.as_str().ok_or_else(|| wrong("solver", "a string"))?.to_string();
+        }
+        if let Some(epoch) = config.get("epoch") {
+            self.epoch = epoch.as_str().ok_or_else(|| wrong("epoch", "a string"))?.to_string();
+        }
+        if let Some(boards) = config.get("boards") {
+            self.boards = boards.as_u64().ok_or_else(|| wrong("boards", "a number"))? as usize;
+        }
         if let Some(is_random) = config.get("random") {
-            self.is_random = is_random.as_bool().expect("config file error");
+            self.is_random = is_random.as_bool().ok_or_else(|| wrong("random", "true or false"))?;
+        }
+        if let Some(is_weighted) = config.get("weighted") {
+            self.is_weighted = is_weighted.as_bool().ok_or_else(|| wrong("weighted", "true or false"))?;
         }
         if let Some(is_difficult) = config.get("difficult") {
-            self.is_difficult = is_difficult.as_bool().expect("config file error");
+            self.is_difficult = is_difficult.as_bool().ok_or_else(|| wrong("difficult", "true or false"))?;
+        }
+        if let Some(is_strict) = config.get("strict") {
+            self.is_strict = is_strict.as_bool().ok_or_else(|| wrong("strict", "true or false"))?;
+        }
+        if let Some(is_adversarial) = config.get("adversarial") {
+            self.is_adversarial = is_adversarial.as_bool().ok_or_else(|| wrong("adversarial", "true or false"))?;
         }
         if let Some(is_stats) = config.get("stats") {
-            self.is_stats = is_stats.as_bool().expect("config file error");
+            self.is_stats = is_stats.as_bool().ok_or_else(|| wrong("stats", "true or false"))?;
+        }
+        if let Some(is_share) = config.get("share") {
+            self.is_share = is_share.as_bool().ok_or_else(|| wrong("share", "true or false"))?;
         }
         if let Some(day) = config.get("day") {
             self.is_special_day = true;
-            self.day = day.as_i64().expect("config file error") as i32;
+            self.day = day.as_i64().ok_or_else(|| wrong("day", "a number"))? as i32;
         }
         if let Some(seed) = config.get("seed") {
             self.is_seeded = true;
-            self.seed = seed.as_u64().expect("config file error");
+            self.seed = seed.as_u64().ok_or_else(|| wrong("seed", "a number"))?;
         }
         if let Some(final_set_path) = config.get("final_set") {
-            self.final_path = final_set_path.as_str().expect("config file error").to_string();
+            self.final_path = final_set_path.as_str().ok_or_else(|| wrong("final_set", "a string"))?.to_string();
         }
         if let Some(acceptable_path) = config.get("acceptable_set") {
-            self.acceptable_path = acceptable_path.as_str().expect("config file error").to_string();
+            self.acceptable_path = acceptable_path.as_str().ok_or_else(|| wrong("acceptable_set", "a string"))?.to_string();
         }
         if let Some(state_path) = config.get("state") {
             self.is_stated = true;
-            self.state_path = state_path.as_str().expect("config file error").to_string();
-            if let Ok(state_string) = fs::read_to_string(&self.state_path) {
-                if state_string != "{}" {
-                    self.state = serde_json::from_str(&state_string).expect("config file error")
-                }
-            }
+            self.state_path = state_path.as_str().ok_or_else(|| wrong("state", "a string"))?.to_string();
+            self.state = load_state(&self.state_path)?;
+        }
+        if let Some(pattern_cache_path) = config.get("pattern_cache") {
+            self.pattern_cache_path = pattern_cache_path.as_str().ok_or_else(|| wrong("pattern_cache", "a string"))?.to_string();
         }
         if let Some(word) = config.get("word") {
             self.is_word_specified = true;
-            *word_to_guess = word.as_str().expect("config file error").to_string();
+            *word_to_guess = word.as_str().ok_or_else(|| wrong("word", "a string"))?.to_string();
+        }
+        Ok(())
+    }
+
+    /// Apply the options of a request to the HTTP server to a copy of the info of the server
+    /// Flags of the server override them as they override config files, a new lang brings its built-in lists
+    pub fn apply_request(&mut self, options: &serde_json::Value) -> Result<(), Error> {
+        let (lang, seed) = (self.lang.clone(), self.seed);
+        let flags: serde_json::Map<String, serde_json::Value> = options.as_object().into_iter().flatten()
+            .filter(|(key, _)| self.sources.get(*key) == Some(&ConfigSource::CommandLine))
+            .map(|(key, _)| (key.clone(), config_value(self, key, "")))
+            .collect();
+        self.load_config(&mut String::new(), options, &ConfigSource::Request)?;
+        self.load_config(&mut String::new(), &serde_json::Value::Object(flags), &ConfigSource::CommandLine)?;
+        if self.lang != lang {
+            let pack = language_pack(&self.lang)?;
+            self.use_pack_lists(pack);
+            self.use_pack_keys(pack);
+        }
+        if self.lang != lang || self.seed != seed {
+            self.shuffle_answers();
+        }
+        if self.max_guesses == 0 {
+            return Err(Error::InvalidArgs("max guesses and boards should be at least 1".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Use the built-in lists of the pack, except a list given by a file
+    fn use_pack_lists(&mut self, pack: &lang::LanguagePack) {
+        if self.final_path.is_empty() {
+            self.final_set = pack.final_words.iter().map(|x| x.to_string()).collect();
+            self.final_weights = vec![1; self.final_set.len()];
+        }
+        if self.acceptable_path.is_empty() {
+            self.acceptable_set = Arc::new(pack.acceptable_words.iter().map(|x| x.to_string()).collect());
+        }
+    }
+
+    /// Choose the alphabet and keyboard for the lists, the keyboard of the pack unless the lists have letters it misses
+    fn use_pack_keys(&mut self, pack: &lang::LanguagePack) {
+        self.alphabet = if self.final_path.is_empty() && self.acceptable_path.is_empty() {
+            pack.alphabet.to_vec()
+        } else {
+            alphabet_of(&[&self.final_set, &self.acceptable_set])
+        };
+        self.keyboard = if self.alphabet.iter().all(|x| pack.keyboard.iter().any(|row| row.contains(*x))) {
+            pack.keyboard.iter().map(|row| row.to_string()).collect()
+        } else {
+            keyboard_of(&self.alphabet)
+        };
+    }
+
+    /// Shuffle the answers of random mode by seed
+    fn shuffle_answers(&mut self) {
+        let mut temp: Vec<usize> = (0..self.final_set.len()).collect();
+        let mut rng: StdRng = SeedableRng::seed_from_u64(self.seed);
+        temp.shuffle(&mut rng);
+        self.shuffled_seq = temp;
+    }
+
+    /// Choose the answer of next round or board in random mode by day and answers chosen before
+    pub fn next_random_answer(&mut self) -> String {
+        let mut word_to_guess = String::new();
+        //one word for each board of each round played
+        let start_day = self.words_appeared.len() as i32 + self.day - 1;//cause do not exist day0
+        get_word_by_start_day(&mut word_to_guess, self, start_day);
+        self.words_appeared.push(word_to_guess.clone());
+        word_to_guess
+    }
+
+    /// Index in final set of the answer of a day, the same for the same seed and day
+    /// In weighted mode each day draws a word in proportion to its frequency, otherwise days go through shuffled_seq
+    pub fn answer_index(&self, day: usize) -> usize {
+        if !self.is_weighted {
+            return self.shuffled_seq[day % self.shuffled_seq.len()];
+        }
+        let mut rng: StdRng = SeedableRng::seed_from_u64(self.seed ^ (day as u64).wrapping_mul(0x9e3779b97f4a7c15));
+        let mut target = rng.gen_range(0..self.final_weights.iter().sum::<u64>());
+        for (i, weight) in self.final_weights.iter().enumerate() {
+            if target < *weight {
+                return i;
+            }
+            target -= weight;
+        }
+        self.final_weights.len() - 1
+    }
+
+    /// Indexes of games in state chosen by --game or --last, or all games
+    pub fn replay_range(&self) -> Range<usize> {
+        let total = self.state.games.len();
+        if self.replay_game > 0 {
+            self.replay_game - 1..self.replay_game
+        } else if self.replay_last > 0 {
+            total - self.replay_last.min(total)..total
+        } else {
+            0..total
+        }
+    }
+
+    /// Every problem of the word lists given to `lists check`, with the path of its list
+    pub fn check_lists(&self) -> Result<Vec<(String, lists::Problem)>, Error> {
+        lists::check_lists(&self.final_path, &self.acceptable_path, self.word_length)
+    }
+
+    /// "builtin" for the built-in English lists, "builtin-LANG" for other languages,
+    /// or the fingerprint of both lists in hex
+    pub fn word_list_id(&self) -> String {
+        if !self.final_path.is_empty() || !self.acceptable_path.is_empty() {
+            format!("{:016x}", word_list_fingerprint(&self.acceptable_set, &self.final_set))
+        } else if self.lang == lang::DEFAULT_LANG {
+            "builtin".to_string()
+        } else {
+            format!("builtin-{}", self.lang)
         }
     }
+
+    /// Number of guesses of a round, one more for each extra board
+    pub fn turn_budget(&self) -> usize {
+        self.max_guesses + self.boards - 1
+    }
+
+    /// Record a finished round into state
+    pub fn record_session(&mut self, session: &GameSession) {
+        self.record_game(session.to_game());
+    }
+
+    /// Record a finished multi-board round into state
+    pub fn record_multi_session(&mut self, session: &MultiSession) {
+        self.record_game(session.to_game());
+    }
+
+    fn record_game(&mut self, mut game: Game) {
+        let day = self.round_day(&game);
+        self.annotate_game(&mut game, day);
+        let is_success = game.is_success();
+        self.state.games.push(game);
+        self.state.total_rounds += 1;
+        if is_success {
+            self.succeeded_game += 1;
+        } else {
+            self.failed_game += 1;
+        }
+    }
+
+    /// Day of random mode of a round just played, its answers are the last ones drawn
+    pub fn round_day(&self, game: &Game) -> i32 {
+        self.day + self.words_appeared.len() as i32 - game.answers.len().max(1) as i32
+    }
+
+    /// Fill in the date, time, options and word lists of a finished game, day is the one its answer is drawn from
+    pub fn annotate_game(&self, game: &mut Game, day: i32) {
+        if !self.date.is_empty() {
+            game.date = Some(self.date.clone());
+        }
+        game.timestamp = Some(daily::timestamp());
+        game.mode = Some(GameMode {
+            difficult: self.is_difficult,
+            random: self.is_random,
+            seed: if self.is_random { Some(self.seed) } else { None },
+            day: if self.is_random { Some(day) } else { None },
+            weighted: self.is_weighted && (self.is_random || !self.date.is_empty()),
+        });
+        game.word_list = Some(self.word_list_id());
+    }
+
+    /// The alphabet a saved game was played with: the pack of "builtin-LANG", the current one for
+    /// the current lists or a game without word_list, otherwise the letters of its own words
+    pub fn game_alphabet(&self, game: &Game) -> Vec<char> {
+        let id = match &game.word_list {
+            Some(id) if *id != self.word_list_id() => id,
+            _ => return self.alphabet.clone(),
+        };
+        let name = if id == "builtin" { Some(lang::DEFAULT_LANG) } else { id.strip_prefix("builtin-") };
+        if let Some(pack) = name.and_then(lang::pack) {
+            return pack.alphabet.to_vec();
+        }
+        let words: Vec<String> = game.guesses.iter().chain(&game.answers).chain([&game.answer])
+            .map(|x| x.to_lowercase()).collect();
+        alphabet_of(&[&words])
+    }
 }
 
 pub struct RoundInfo {
     already_guessed_position: Vec<(i32, char)>,
+    pub alphabet: Vec<char>,
+    /// Colors of the letters, in order of alphabet
     pub alphabet_color: Vec<Color>,
     pub word_guessed_this_round: Vec<String>,
     hint_list: Vec<String>,
@@ -151,26 +554,274 @@ impl RoundInfo {
     pub fn new(info: &Info) -> RoundInfo {
         let round_info = RoundInfo {
             already_guessed_position: vec![],
-            alphabet_color: {
-                let mut alphabet: Vec<Color> = vec![];
-                for _i in 0..26 {
-                    let temp = Color::X;
-                    alphabet.push(temp);
-                }
-                alphabet
-            },
+            alphabet: info.alphabet.clone(),
+            alphabet_color: vec![Color::X; info.alphabet.len()],
             word_guessed_this_round: vec![],
-            hint_list: info.acceptable_set.clone(),
+            hint_list: if info.is_hint { info.acceptable_set.to_vec() } else { vec![] },
         };
         round_info
     }
 }
 
+/// Result of an accepted guess
+pub struct GuessOutcome {
+    pub guess: String,
+    pub colors: Vec<Color>,
+    pub is_correct: bool,
+}
+
+/// A round of game, which only receives guesses as strings and never reads or prints
+pub struct GameSession {
+    answer: String,
+    is_adversarial: bool,
+    /// Answers still consistent with every guess in adversarial mode
+    candidates: Vec<String>,
+    acceptable_set: Arc<Vec<String>>,
+    is_difficult: bool,
+    is_strict: bool,
+    is_hint: bool,
+    max_guesses: usize,
+    pattern_table: Option<Arc<PatternTable>>,
+    round_info: RoundInfo,
+    history: Vec<(String, Vec<Color>)>,
+}
+
+impl GameSession {
+    /// Start a round with options in info and the answer of this round
+    /// In adversarial mode the answer is ignored, and decided as late as possible
+    pub fn new(info: &Info, answer: &str) -> GameSession {
+        let candidates = if info.is_adversarial { info.final_set.clone() } else { vec![] };
+        GameSession {
+            answer: match candidates.first() {
+                Some(candidate) => candidate.clone(),
+                None => answer.to_lowercase(),
+            },
+            is_adversarial: info.is_adversarial,
+            candidates,
+            acceptable_set: info.acceptable_set.clone(),
+            is_difficult: info.is_difficult,
+            is_strict: info.is_strict,
+            is_hint: info.is_hint,
+            max_guesses: info.max_guesses,
+            pattern_table: info.pattern_table.clone(),
+            round_info: RoundInfo::new(info),
+            history: vec![],
+        }
+    }
+
+    /// Check and apply a guess, return the colors of it
+    /// Return a result with Error if the guess is not allowed or the round is over
+    pub fn submit_guess(&mut self, guess: &str) -> Result<GuessOutcome, Error> {
+        let guess_word = self.check_guess(guess)?;
+        //Here, the input is finally valid enough
+        self.round_info.word_guessed_this_round.push(to_upper_word(&guess_word));
+        if self.is_adversarial {
+            self.keep_largest_bucket(&guess_word);
+        }
+        let word_result = calculate_color(&self.answer, &guess_word);
+        for (i, (color, letter)) in word_result.iter().zip(guess_word.chars()).enumerate() {
+            if let Color::G = color {
+                self.round_info.already_guessed_position.push((i as i32, letter))
+            }
+        }
+        if self.is_hint {
+            self.round_info.hint_list = solver::filter_candidates(
+                &self.round_info.hint_list, &guess_word, pattern::encode_colors(&word_result), self.pattern_table.as_deref());
+        }
+        update_round_alphabet_color(&mut self.round_info, &guess_word, &word_result);
+        self.history.push((guess_word.clone(), word_result.clone()));
+        Ok(GuessOutcome {
+            is_correct: guess_word == self.answer,
+            guess: guess_word,
+            colors: word_result,
+        })
+    }
+
+    /// Check a guess without applying it, return the guess string
+    pub fn check_guess(&self, guess: &str) -> Result<String, Error> {
+        if self.is_over() {
+            return Err(Error::GameOver);
+        }
+        let guess_word = get_checked_guess(guess, &self.acceptable_set, self.is_difficult, &self.round_info)?;
+        if self.is_strict && !is_consistent_guess(&guess_word, &self.history) {
+            return Err(Error::InvalidWord("not consistent with former colors"));
+        }
+        Ok(guess_word)
+    }
+
+    /// Group possible answers by the pattern of the guess, keep the largest group,
+    /// and prefer the pattern with fewer letters matched when groups are equally large, then the smaller pattern
+    /// Any answer left gives the same colors, so the first one is used as answer until the next guess
+    fn keep_largest_bucket(&mut self, guess_word: &str) {
+        let mut buckets: HashMap<pattern::Pattern, Vec<String>> = HashMap::new();
+        for candidate in &self.candidates {
+            buckets.entry(pattern::get_pattern(candidate, guess_word)).or_insert(vec![]).push(candidate.clone());
+        }
+        if let Some((_, bucket)) = buckets.into_iter()
+            .max_by(|a, b| a.1.len().cmp(&b.1.len())
+                .then(pattern::matched_letters(b.0).cmp(&pattern::matched_letters(a.0)))
+                .then(b.0.cmp(&a.0))) {
+            self.candidates = bucket;
+            self.answer = self.candidates[0].clone();
+        }
+    }
+
+    /// The round is over when the answer is guessed or no guess is left
+    pub fn is_over(&self) -> bool {
+        self.is_success() || self.history.len() >= self.max_guesses
+    }
+
+    pub fn is_success(&self) -> bool {
+        match self.history.last() {
+            Some(last) => last.0 == self.answer,
+            None => false,
+        }
+    }
+
+    pub fn answer(&self) -> &String {
+        &self.answer
+    }
+
+    /// Colors of the letters, in order of alphabet()
+    pub fn keyboard(&self) -> &Vec<Color> {
+        &self.round_info.alphabet_color
+    }
+
+    /// Letters of the keyboard
+    pub fn alphabet(&self) -> &Vec<char> {
+        &self.round_info.alphabet
+    }
+
+    /// Guesses of this round and their colors
+    pub fn history(&self) -> &Vec<(String, Vec<Color>)> {
+        &self.history
+    }
+
+    /// Answers still possible, only updated in hint mode
+    pub fn hint_list(&self) -> &Vec<String> {
+        &self.round_info.hint_list
+    }
+
+    pub fn guesses_left(&self) -> usize {
+        self.max_guesses - self.history.len()
+    }
+
+    /// Convert this round to a game to save in state
+    pub fn to_game(&self) -> Game {
+        Game {
+            answer: to_upper_word(&self.answer),
+            answers: vec![],
+            guesses: self.round_info.word_guessed_this_round.clone(),
+            max_guesses: self.max_guesses,
+            date: None,
+            timestamp: None,
+            mode: None,
+            word_list: None,
+            outcome: Some(if self.is_success() { Outcome::Win } else { Outcome::Loss }),
+        }
+    }
+}
+
+/// Several boards guessed with the same guesses, each board is a GameSession
+/// A solved board receives no more guesses
+pub struct MultiSession {
+    boards: Vec<GameSession>,
+    guesses: Vec<String>,
+    max_guesses: usize,
+}
+
+impl MultiSession {
+    /// Start a round with options in info and the answers of every board
+    pub fn new(info: &Info, answers: &[String]) -> MultiSession {
+        let max_guesses = info.turn_budget();
+        MultiSession {
+            boards: answers.iter().map(|answer| {
+                let mut board = GameSession::new(info, answer);
+                board.max_guesses = max_guesses;
+                board
+            }).collect(),
+            guesses: vec![],
+            max_guesses,
+        }
+    }
+
+    /// Check the guess on every unsolved board, then apply it to them
+    /// Return the outcome of each board, None for boards solved before
+    pub fn submit_guess(&mut self, guess: &str) -> Result<Vec<Option<GuessOutcome>>, Error> {
+        if self.is_over() {
+            return Err(Error::GameOver);
+        }
+        for board in self.boards.iter().filter(|x| !x.is_over()) {
+            board.check_guess(guess)?;
+        }
+        let mut outcomes = vec![];
+        for board in self.boards.iter_mut() {
+            if board.is_over() {
+                outcomes.push(None);
+            } else {
+                outcomes.push(Some(board.submit_guess(guess)?));
+            }
+        }
+        self.guesses.push(guess.to_string());
+        Ok(outcomes)
+    }
+
+    /// The round is over when every board is solved or no guess is left
+    pub fn is_over(&self) -> bool {
+        self.is_success() || self.guesses.len() >= self.max_guesses
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.boards.iter().all(|board| board.is_success())
+    }
+
+    pub fn boards(&self) -> &Vec<GameSession> {
+        &self.boards
+    }
+
+    pub fn guesses(&self) -> &Vec<String> {
+        &self.guesses
+    }
+
+    /// Convert this round to a game with every answer to save in state
+    pub fn to_game(&self) -> Game {
+        let answers: Vec<String> = self.boards.iter().map(|x| to_upper_word(&x.answer)).collect();
+        Game {
+            answer: answers[0].clone(),
+            answers,
+            guesses: self.guesses.iter().map(|x| to_upper_word(x)).collect(),
+            max_guesses: self.max_guesses,
+            date: None,
+            timestamp: None,
+            mode: None,
+            word_list: None,
+            outcome: Some(if self.is_success() { Outcome::Win } else { Outcome::Loss }),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
-    InvalidWord,
-    AlreadyCorrect,
-    InvalidArgs,
+    /// A guess that is not accepted with the reason, printed as INVALID during a round
+    InvalidWord(&'static str),
+    /// A guess after the round is over
+    GameOver,
+    /// Wrong command line, with a message for the user
+    Usage(String),
+    /// Options out of range or used together when they cannot be
+    InvalidArgs(String),
+    /// A value that cannot be parsed, with what it should look like
+    Parse { value: String, expected: &'static str },
+    /// A config file with wrong syntax or a wrong value
+    Config { path: String, message: String },
+    /// A file that cannot be read or written
+    Io { path: String, source: std::io::Error },
+    /// A word list with a wrong word, line starts from 1
+    WordList { path: String, line: usize, message: String },
+    /// A state file that cannot be parsed
+    State { path: String, message: String },
+    /// The daily puzzle of the date is already in state
+    AlreadyPlayed(String),
 }
 
 #[derive(Debug)]
@@ -209,290 +860,340 @@ impl Color {
     }
 }
 
-impl Error {
-    pub fn to_string(&self) -> String {
-        return match &self {
-            Error::InvalidWord => { "INVALID".to_string() }
-            Error::AlreadyCorrect => { "CORRECT".to_string() }
-            Error::InvalidArgs => { "InvalidArgs".to_string() }
-        };
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidWord(_) => write!(f, "INVALID"),
+            Error::GameOver => write!(f, "the round is over"),
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::InvalidArgs(message) => write!(f, "{}", message),
+            Error::Parse { value, expected } => write!(f, "cannot parse '{}', expected {}", value, expected),
+            Error::Config { path, message } => write!(f, "config file {}: {}", path, message),
+            Error::Io { path, source } => write!(f, "{}: {}", path, source),
+            Error::WordList { path, line, message } => write!(f, "word list {} line {}: {}", path, line, message),
+            Error::State { path, message } => write!(f, "state file {}: {}", path, message),
+            Error::AlreadyPlayed(date) => write!(f, "the puzzle of {} was already played", date),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
     }
 }
 
 
-/// Analyze args to change info
+/// Analyze args to change info, after the config files in configs, usually found by config::discover
 /// Return a result with Error, invalid input or args
-pub fn info_analyze(word_to_guess: &mut String, info: &mut Info, args: &Vec<String>) -> Result<(), Error> {
-    let mut num_args = 0;
-    //loop to analyze args
-    //first load config
-    loop {
-        match args.iter().nth(num_args) {
-            //first decide sets.clone().
-            None => break,
-            Some(arg) => {
-                match &arg[..] {
-                    "-c" | "--config" => {
-                        let config_path = args.iter().nth(num_args + 1).expect("did not input word").clone();
-                        let config_string = fs::read_to_string(&config_path).expect("config file error");
-                        let config: serde_json::Value = serde_json::from_str(&config_string).expect("config file error");
-                        info.load_config(word_to_guess, &config);
-                    }
-                    _ => {}
-                }
-            }
+pub fn info_analyze(word_to_guess: &mut String, info: &mut Info, args: &[String], configs: &[ConfigSource]) -> Result<(), Error> {
+    let args = Args::parse(args)?;
+    info.command = args.command;
+    if info.command == Command::Help || info.command == Command::Version {
+        return Ok(());
+    }
+    //first load config files from user, project and --config, flags override them
+    let mut sources = configs.to_vec();
+    if let Some(config_path) = args.value("--config") {
+        sources.push(ConfigSource::File(config_path.clone()));
+    }
+    for source in &sources {
+        if let Some(path) = source.path() {
+            let config = config::read_config(path)?;
+            info.load_config(word_to_guess, &config, source)?;
         }
-        num_args += 1;
     }
-    //next decide sets
-    let mut num_args = 0;
-    loop {
-        match args.iter().nth(num_args) {
-            //first decide sets.clone().
-            None => break,
-            Some(arg) => {
-                match &arg[..] {
-                    "-f" | "--final-set" => {
-                        info.final_path = args.iter().nth(num_args + 1).expect("did not input word").clone();
-                    }
-                    "-a" | "--acceptable-set" => {
-                        info.acceptable_path = args.iter().nth(num_args + 1).expect("did not input word").clone();
-                    }
-                    _ => {}
-                }
-            }
+    for long in args.flags() {
+        if let Some(key) = config::flag_key(long) {
+            info.sources.insert(key.to_string(), ConfigSource::CommandLine);
         }
-        num_args += 1;
     }
-    // println!("TEST point2");
+    //next decide sets
+    if let Some(path) = args.value("--final-set") {
+        info.final_path = path.clone();
+    }
+    if let Some(path) = args.value("--acceptable-set") {
+        info.acceptable_path = path.clone();
+    }
+    if let Some(path) = args.value("--pattern-cache") {
+        info.pattern_cache_path = path.clone();
+    }
+    if let Some(lang) = args.value("--lang") {
+        info.lang = lang.clone();
+    }
+    if let Some(length) = args.parse_value("--length")? {
+        info.word_length = length;
+    }
+    //lists check only reads the lists given to it
+    if info.command == Command::Lists {
+        info.final_path = args.operands[0].clone();
+        info.acceptable_path = args.operands[1].clone();
+        return Ok(());
+    }
+    if info.word_length < MIN_WORDLE_LENS || info.word_length > MAX_WORDLE_LENS {
+        return Err(Error::InvalidArgs(format!(
+            "word length should be from {} to {}", MIN_WORDLE_LENS, MAX_WORDLE_LENS)));
+    }
+    //built-in sets only have words of default length
+    if info.word_length != WORDLE_LENS && (info.final_path.is_empty() || info.acceptable_path.is_empty()) {
+        return Err(Error::InvalidArgs(format!(
+            "words of length {} need both final set and acceptable set", info.word_length)));
+    }
+    //built-in lists of the language, -f and -a replace them
+    let pack = language_pack(&info.lang)?;
+    if info.lang != lang::DEFAULT_LANG {
+        info.use_pack_lists(pack);
+    }
     if !info.final_path.is_empty() {
-        set_from_path(&info.final_path, &mut info.final_set);
+        info.final_weights = set_from_path(&info.final_path, &mut info.final_set, info.word_length)?;
     }
     if !info.acceptable_path.is_empty() {
-        set_from_path(&info.acceptable_path, &mut info.acceptable_set);
+        set_from_path(&info.acceptable_path, Arc::make_mut(&mut info.acceptable_set), info.word_length)?;
     }
-    //verify specified sets' contain relationship
-    let mut is_contain: bool = true;
-    for temp in &info.final_set {
-        if !info.acceptable_set.contains(&temp) {
-            is_contain = false;
+    info.use_pack_keys(pack);
+    if !info.pattern_cache_path.is_empty() {
+        info.pattern_table = Some(Arc::new(
+            PatternTable::new(&info.acceptable_set, &info.final_set, &info.pattern_cache_path)?));
+    }
+    //verify specified sets' contain relationship, acceptable set is sorted
+    if let Some(word) = info.final_set.iter().find(|x| info.acceptable_set.binary_search(x).is_err()) {
+        return Err(Error::InvalidArgs(format!("'{}' in final set is not in acceptable set", word)));
+    }
+    //then the other flags
+    if let Some(word) = args.value("--word") {
+        info.is_word_specified = true;
+        *word_to_guess = word.clone();
+        if !info.final_set.contains(word_to_guess) {
+            return Err(Error::InvalidArgs(format!("'{}' is not in the final set", word)));
         }
     }
-    if !is_contain {
-        return Err(Error::InvalidArgs);
+    if args.has("--random") {
+        info.is_random = true;
     }
-    // println!("ser valid");
-    let mut num_args = 0;
-    loop {
-        //loop to analyze args
-        match args.iter().nth(num_args) {
-            //first decide sets.clone().
-            None => break,
-            Some(arg) => {
-                match &arg[..] {
-                    "-w" | "--word" => {
-                        info.is_word_specified = true;
-                        *word_to_guess = args.iter().nth(num_args + 1).expect("did not input word").clone();
-                        assert!(FINAL.contains(&&word_to_guess[..]), "Input illegal! ");
-                    }
-                    "-r" | "--random" => {
-                        info.is_random = true
-                    }
-                    "-D" | "--difficult" => {
-                        info.is_difficult = true
-                    }
-                    "-t" | "--stats" => {
-                        info.is_stats = true
-                    }
-                    "-d" | "--day" => {
-                        info.is_special_day = true;
-                        info.day =
-                            args.iter().nth(num_args + 1).expect("did not input day").clone().parse().unwrap();
-                    }
-                    "-s" | "--seed" => {
-                        info.is_seeded = true;
-                        info.seed =
-                            args.iter().nth(num_args + 1).expect("did not input seed").clone().parse().unwrap();
-                    }
-                    "-S" | "--state" => {
-                        info.is_stated = true;
-                        info.state_path = args.iter().nth(num_args + 1).expect("did not input word").clone();
-                        if let Ok(state_string) = fs::read_to_string(&info.state_path) {
-                            if state_string != "{}" {
-                                info.state = match serde_json::from_str(&state_string) {
-                                    Ok(x) => x,
-                                    Err(_) => {
-                                        println!("cannot match");
-                                        return Err(Error::InvalidArgs);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    "-h" | "--hint" => {
-                        info.is_hint = true;
-                    }
-                    "-c" | "--recommend" => { info.is_recommend = true }
-                    _ => {}
-                }
-            }
+    if args.has("--weighted") {
+        info.is_weighted = true;
+    }
+    if args.has("--difficult") {
+        info.is_difficult = true;
+    }
+    if args.has("--daily") {
+        info.date = daily::today();
+    }
+    if let Some(date) = args.value("--date") {
+        info.date = date.clone();
+    }
+    if args.has("--strict") {
+        info.is_strict = true;
+    }
+    if args.has("--adversarial") {
+        info.is_adversarial = true;
+    }
+    if args.has("--stats") {
+        info.is_stats = true;
+    }
+    if let Some(day) = args.parse_value("--day")? {
+        info.is_special_day = true;
+        info.day = day;
+    }
+    if let Some(seed) = args.parse_value("--seed")? {
+        info.is_seeded = true;
+        info.seed = seed;
+    }
+    if let Some(path) = args.value("--state") {
+        info.is_stated = true;
+        info.state_path = path.clone();
+        info.state = load_state(&info.state_path)?;
+    }
+    if let Some(port) = args.parse_value("--port")? {
+        info.port = port;
+    }
+    if let Some(dir) = args.value("--state-dir") {
+        info.state_dir = dir.clone();
+    }
+    if args.has("--http") {
+        info.is_http = true;
+    }
+    if let Some(protocol) = args.value("--protocol") {
+        match protocol.as_str() {
+            "text" => {}
+            "jsonl" => info.is_jsonl = true,
+            _ => return Err(Error::InvalidArgs(format!("unknown protocol '{}', available: text, jsonl", protocol))),
         }
-        num_args += 1;
+    }
+    if args.has("--hint") {
+        info.is_hint = true;
+    }
+    if args.has("--recommend") {
+        info.is_recommend = true;
+    }
+    if args.has("--share") {
+        info.is_share = true;
+    }
+    if let Some(game) = args.parse_value("--game")? {
+        info.replay_game = game;
+    }
+    if let Some(last) = args.parse_value("--last")? {
+        info.replay_last = last;
+    }
+    if let Some(solver) = args.value("--solver") {
+        info.solver = solver.clone();
+    }
+    if !solver::SOLVERS.contains(&info.solver.as_str()) {
+        return Err(Error::InvalidArgs(format!(
+            "unknown solver '{}', available: {}", info.solver, solver::SOLVERS.join(", "))));
+    }
+    if let Some(opening) = args.value("--opening") {
+        info.opening = opening.clone();
+        if !info.acceptable_set.contains(&info.opening) {
+            return Err(Error::InvalidArgs(format!("'{}' is not in the acceptable set", opening)));
+        }
+    }
+    if let Some(boards) = args.parse_value("--boards")? {
+        info.boards = boards;
+    }
+    if let Some(max_guesses) = args.parse_value("--max-guesses")? {
+        info.max_guesses = max_guesses;
     }
 
-    if info.is_seeded {
-        info.shuffled_seq = {
-            let mut temp: Vec<usize> = (0..info.final_set.len()).collect();
-            let mut rng: StdRng = SeedableRng::seed_from_u64(info.seed);
-            temp.shuffle(&mut rng);
-            temp
+    if info.is_seeded || !info.final_path.is_empty() || info.lang != lang::DEFAULT_LANG {
+        info.shuffle_answers();
+    }
+    if info.command == Command::Config {
+        return Ok(());
+    }
+    if info.max_guesses == 0 || info.boards == 0 {
+        return Err(Error::InvalidArgs("max guesses and boards should be at least 1".to_string()));
+    }
+    //replay needs a state file and at most one way to choose games
+    if info.command == Command::Stats && !info.is_stated {
+        return Err(Error::Usage("stats needs a state file".to_string()));
+    }
+    if info.command == Command::Replay {
+        if !info.is_stated {
+            return Err(Error::Usage("replay needs a state file".to_string()));
+        }
+        if info.replay_game > 0 && info.replay_last > 0 {
+            return Err(Error::Usage("--game and --last cannot be used together".to_string()));
+        }
+        if info.replay_game > info.state.games.len() {
+            return Err(Error::InvalidArgs(format!("there are only {} games in state", info.state.games.len())));
+        }
+        return Ok(());
+    }
+    //every player of the server gets answers by the rounds played, like random mode
+    if info.command == Command::Serve {
+        if info.boards > 1 || info.is_word_specified || !info.date.is_empty() {
+            return Err(Error::InvalidArgs("serve only plays random answers on one board".to_string()));
         }
+        info.is_random = !info.is_adversarial;
+        return Ok(());
+    }
+    if info.boards > 1 && (info.is_adversarial || info.is_word_specified) {
+        return Err(Error::InvalidArgs("several boards cannot be adversarial or of a given word".to_string()));
+    }
+    //a guess fitting the colors of one board would break the hard mode of the others
+    if info.boards > 1 && (info.is_difficult || info.is_strict) {
+        return Err(Error::InvalidArgs("several boards cannot be played in difficult or strict mode".to_string()));
     }
     //deal with conflict args
+    if info.is_adversarial && (info.is_random || info.is_word_specified) {
+        return Err(Error::InvalidArgs("adversarial mode cannot be random or of a given word".to_string()));
+    }
     if info.is_random {
         if info.is_word_specified {
-            return Err(Error::InvalidArgs);
+            return Err(Error::InvalidArgs("random mode cannot be of a given word".to_string()));
         }
     }
     if info.is_word_specified {
         if info.is_seeded && info.is_special_day {
-            return Err(Error::InvalidArgs);
+            return Err(Error::InvalidArgs("seed and day are only for random mode".to_string()));
         }
     }
+    //daily puzzle is a word specified by date
+    if !info.date.is_empty() {
+        if info.is_random || info.is_word_specified || info.is_adversarial || info.boards > 1 {
+            return Err(Error::InvalidArgs(
+                "daily puzzle cannot be random, adversarial, of a given word or several boards".to_string()));
+        }
+        let number = daily::puzzle_number(&info.date, &info.epoch)?;
+        if info.state.games.iter().any(|game| game.date.as_ref() == Some(&info.date)) {
+            return Err(Error::AlreadyPlayed(info.date.clone()));
+        }
+        info.is_word_specified = true;
+        *word_to_guess = info.final_set[info.answer_index(number)].clone();
+    }
     Ok(())
 }
 
-/// Receives the word to guess this round, then starts a round of game
-/// Return a result with Error
-pub fn guess_round(mut word_to_guess: &mut String, mut info: &mut Info) -> Result<(), Error> {
-    let is_tty = atty::is(atty::Stream::Stdout);
-    let game_time = info.failed_game + info.succeeded_game;
-    let mut is_success: bool = false;
-    let mut guess_times = 0;
-    let mut round_info = RoundInfo::new(&info);
-
-    //initialize alphabet of color
-    if info.is_random {
-        let start_day = game_time + info.day - 1;//cause do not exist day0
-        get_word_by_start_day(&mut word_to_guess, info, start_day);
-        info.words_appeared.push(word_to_guess.clone());
-    } else if !info.is_word_specified {
-        word_to_guess.clear();
-        if is_tty {
-            println!("Default mode, please input the answer you set ");
-        }
-        io::stdin().read_line(&mut word_to_guess).unwrap();
-        word_to_guess.pop();
-        assert!(FINAL.contains(&&word_to_guess[..]), "Input illegal! ");
-    }
-    if is_tty {
-        println!("This is round {}, please input your guesses",
-                 console::style(info.state.total_rounds + 1).green().bold());
-    }
-    *word_to_guess = word_to_guess.to_ascii_lowercase();
-    while guess_times <= 5 {
-        //Guess 6 times
-
-        match guess_one_time(word_to_guess, &mut info, &mut round_info) {
-            Err(error) => {
-                match error {
-                    Error::InvalidWord => { println!("{}", error.to_string()) }
-                    Error::AlreadyCorrect => {
-                        guess_times += 1;
-                        is_success = true;
-                        print_alphabet(&mut round_info.alphabet_color);
-                        println!("{} {}", error.to_string(), guess_times);
-                        break;
-                    }
-                    Error::InvalidArgs => {}
-                }
-            }
-            Ok(_) => {
-                guess_times += 1;
-                print_alphabet(&mut round_info.alphabet_color);
-            }
-        }
+/// Receive the path of a state file, return the state in it, upgraded to the current version
+/// A missing or `{}` file is an empty state, it is created when saving
+/// Return a result with Error if the file cannot be read or parsed, or is of a newer version
+pub fn load_state(path: &str) -> Result<State, Error> {
+    let state_string = match fs::read_to_string(path) {
+        Ok(x) => x,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(State::new()),
+        Err(source) => return Err(Error::Io { path: path.to_string(), source }),
+    };
+    if state_string == "{}" {
+        return Ok(State::new());
+    }
+    let mut state: State = serde_json::from_str(&state_string)
+        .map_err(|err| Error::State { path: path.to_string(), message: err.to_string() })?;
+    //saving would drop whatever a newer version added
+    if state.version > STATE_VERSION {
+        return Err(Error::State { path: path.to_string(), message: format!(
+            "version {} is newer than the supported version {}", state.version, STATE_VERSION) });
     }
-    info.state.games.push(Game { answer: word_to_guess.clone().to_ascii_uppercase(), guesses: round_info.word_guessed_this_round });
-    info.state.total_rounds += 1;
+    state.migrate();
+    Ok(state)
+}
 
-    if !is_success {
-        info.failed_game += 1;
-        if is_tty {
-            println!(
-                "{} {}",
-                console::style("FAILED").red().bold(),
-                console::style(word_to_guess.to_ascii_uppercase()).green().italic()
-            );
-        } else {
-            println!("FAILED {}", word_to_guess.to_ascii_uppercase());
+/// FNV-1a of the words allowed to guess and the possible answers, stable between runs and builds
+pub fn word_list_fingerprint(acceptable_set: &[String], final_set: &[String]) -> u64 {
+    let mut fingerprint: u64 = 0xcbf29ce484222325;
+    for word in acceptable_set.iter().chain([String::new()].iter()).chain(final_set.iter()) {
+        for byte in word.bytes().chain([b'\n']) {
+            fingerprint ^= byte as u64;
+            fingerprint = fingerprint.wrapping_mul(0x100000001b3);
         }
-    } else {
-        info.succeeded_game += 1
     }
-    return Ok(());
+    fingerprint
 }
 
 /// Use info and start day to change word to guess
+/// If the word already appeared, use the word of next day
 pub fn get_word_by_start_day(word_to_guess: &mut String, info: &Info, start_day: i32) {
+    let mut day = start_day as usize;
     loop {
-        *word_to_guess = info.final_set.iter().nth(info.shuffled_seq[start_day as usize]).unwrap().to_string();
-        if !info.words_appeared.contains(&word_to_guess) {
+        *word_to_guess = info.final_set[info.answer_index(day)].to_string();
+        if !info.words_appeared.contains(&word_to_guess) || day >= start_day as usize + info.shuffled_seq.len() {
             break;
-        } else {
-            *word_to_guess = info.final_set.iter().nth(info.shuffled_seq[start_day as usize]).unwrap().to_string();
-        }
-    }
-}
-
-/// Take word to guess, info and round info, do guess once.
-/// Calculate the color, print a string of 5 letters, update info
-/// First find G, ignore them, then match last letters one by one (first 5 letters)
-/// For alphabet, use a vec of 5 to record the condition of 5 letters
-pub fn guess_one_time(
-    word_to_guess: &mut String,
-    info: &mut Info,
-    round_info: &mut RoundInfo) -> Result<(), Error> {
-    let guess_word = get_checked_guess(&info, round_info)?;
-    //Here, the input is finally valid enough
-    round_info.word_guessed_this_round.push(guess_word.clone().to_ascii_uppercase());
-
-    let word_result = calculate_color(word_to_guess, &guess_word);
-    for i in 0..WORDLE_LENS {
-        if let Color::G = word_result[i] {
-            round_info.already_guessed_position.push(
-                (i as i32, guess_word.chars().nth(i as usize).unwrap())
-            )
-        }
-    }
-    if info.is_hint {
-        round_info.hint_list = get_new_hint_list(&mut round_info.hint_list, &guess_word, &word_result);
-        println!("total:{}\n{:?}", round_info.hint_list.len(), round_info.hint_list);
-        if info.is_recommend {
-            recommend_from_hint_list(&mut round_info.hint_list);
-        }
-    }
-    //print the match result
-    for i in 0..WORDLE_LENS {
-        let is_tty = atty::is(atty::Stream::Stdout);
-        if is_tty {
-            print!("{}",
-                   match word_result[i] {
-                       Color::Y => console::style(guess_word.chars().nth(i).unwrap()).yellow(),
-                       Color::R => console::style(guess_word.chars().nth(i).unwrap()).black().bright(),
-                       Color::G => console::style(guess_word.chars().nth(i).unwrap()).green(),
-                       Color::X => console::style(guess_word.chars().nth(i).unwrap()).white()
-                   }
-            )
-        } else {
-            print!("{}", word_result[i].to_string());
         }
+        day += 1;
     }
-    print!(" ");
-    //use hash to mark priority
-    update_round_alphabet_color(round_info, &guess_word, &word_result);
-    if guess_word == *word_to_guess { return Err(Error::AlreadyCorrect); }
-    return Ok(());
+}
+
+/// Receive the answer and guesses of a finished game, recompute the result of every guess
+/// and the colors of the letters of alphabet after it
+pub fn replay_guesses(answer: &String, guesses: &Vec<String>, info: &Info, alphabet: &[char]) -> Vec<(GuessOutcome, Vec<Color>)> {
+    let mut round_info = RoundInfo::new(info);
+    round_info.alphabet = alphabet.to_vec();
+    round_info.alphabet_color = vec![Color::X; alphabet.len()];
+    let mut outcomes = vec![];
+    for guess in guesses {
+        let guess = guess.to_lowercase();
+        let colors = calculate_color(answer, &guess);
+        update_round_alphabet_color(&mut round_info, &guess, &colors);
+        let is_correct = guess == answer.to_lowercase();
+        outcomes.push((GuessOutcome { guess, colors, is_correct }, round_info.alphabet_color.clone()));
+        if is_correct {
+            break;
+        }
+    }
+    outcomes
 }
 
 /// Receives a round_info, the word guessed, and the result of it,
@@ -505,9 +1206,9 @@ pub fn update_round_alphabet_color(round_info: &mut RoundInfo, guess_word: &Stri
         ("X".to_string(), 1)
     ]);
     //update alphabet_color
-    for char_num in 0..26 {
-        for position_in_guess in 0..WORDLE_LENS {
-            if ALPHABET[char_num] == guess_word.chars().nth(position_in_guess).unwrap() {
+    for char_num in 0..round_info.alphabet.len() {
+        for (position_in_guess, letter) in guess_word.chars().enumerate().take(word_result.len()) {
+            if round_info.alphabet[char_num] == letter {
                 if color_grade.get(
                     &round_info.alphabet_color[char_num].to_string())
                     <
@@ -520,33 +1221,34 @@ pub fn update_round_alphabet_color(round_info: &mut RoundInfo, guess_word: &Stri
     }
 }
 
-/// Receive two words, and give their match degree in form of color vector
+/// Receive two words of the same length, and give their match degree in form of color vector
 pub fn calculate_color(word_to_guess: &String, guess_word: &String) -> Vec<Color> {
-    let word_to_guess_lower = word_to_guess.to_ascii_lowercase();
-    let guess_word_lower = guess_word.to_ascii_lowercase();
+    let word_to_guess_lower: Vec<char> = word_to_guess.to_lowercase().chars().collect();
+    let guess_word_lower: Vec<char> = guess_word.to_lowercase().chars().collect();
+    let word_length = guess_word_lower.len() as i32;
     let mut word_result: Vec<Color> = vec![];
     let mut correct_position_this_round: Vec<i32> = vec![];
-    for i in 0..WORDLE_LENS as i32 {
-        if guess_word_lower.chars().nth(i as usize) == word_to_guess_lower.chars().nth(i as usize) {
+    for i in 0..word_length {
+        if guess_word_lower.get(i as usize) == word_to_guess_lower.get(i as usize) {
             correct_position_this_round.push(i);
         }
     }
     let mut char_to_ignore_to_guess = correct_position_this_round.clone();
 
     //calculate word_result
-    for position_in_guess in 0..WORDLE_LENS as i32 {
+    for position_in_guess in 0..word_length {
         if correct_position_this_round.contains(&position_in_guess) {
             word_result.push(Color::G);
             continue;
         }
         let mut is_in: bool = false;
-        for position_in_answer in 0..WORDLE_LENS as i32 {
+        for position_in_answer in 0..word_length {
             //skip correct position
             if char_to_ignore_to_guess.contains(&position_in_answer) {
                 continue;
             }
             //mark which letter in goal is appeared in wrong place, make sure G + Y <= actual num
-            if guess_word_lower.chars().nth(position_in_guess as usize) == word_to_guess_lower.chars().nth(position_in_answer as usize) {
+            if guess_word_lower.get(position_in_guess as usize) == word_to_guess_lower.get(position_in_answer as usize) {
                 word_result.push(Color::Y);
                 char_to_ignore_to_guess.push(position_in_answer);
                 is_in = true;
@@ -560,65 +1262,282 @@ pub fn calculate_color(word_to_guess: &String, guess_word: &String) -> Vec<Color
     word_result
 }
 
+/// Receive a finished game and info, return the share text: a header like `Wordle seed 42 day 7 4/6`
+/// and a line of 🟩🟨⬛ for each guess, a `*` after the score marks hard mode
+/// With several boards, the grid of each board ends at the guess that solved it
+pub fn share_to_string(game: &Game, info: &Info) -> String {
+    let mut share = String::from("Wordle");
+    if !info.date.is_empty() {
+        share += &format!(" {}", info.date);
+    } else if info.is_random {
+        share += &format!(" seed {} day {}", info.seed, info.round_day(game));
+    }
+    if game.is_success() {
+        share += &format!(" {}/{}", game.guesses.len(), game.max_guesses);
+    } else {
+        share += &format!(" X/{}", game.max_guesses);
+    }
+    if info.is_difficult || info.is_strict {
+        share += "*";
+    }
+    let answers = if game.answers.is_empty() { vec![game.answer.clone()] } else { game.answers.clone() };
+    for (i, answer) in answers.iter().enumerate() {
+        if i > 0 {
+            share += "\n";
+        }
+        for guess in &game.guesses {
+            share += "\n";
+            for color in calculate_color(answer, guess) {
+                share += match color {
+                    Color::G => "🟩",
+                    Color::Y => "🟨",
+                    _ => "⬛",
+                };
+            }
+            if guess == answer {
+                break;
+            }
+        }
+    }
+    share
+}
+
+/// Receive info and the word given by config or -w, print every setting as a line of TOML
+/// with where its value came from
+pub fn config_to_string(info: &Info, word_to_guess: &str) -> String {
+    let mut lines: Vec<String> = vec![];
+    for key in config::KEYS {
+        let value = config_value(info, key, word_to_guess);
+        let source = info.sources.get(*key).unwrap_or(&ConfigSource::Default);
+        lines.push(format!("{} = {} # {}", key, value, source));
+    }
+    lines.join("\n")
+}
+
+/// The value of a config key in info, as it would be written in a config file
+fn config_value(info: &Info, key: &str, word_to_guess: &str) -> serde_json::Value {
+    match key {
+        "length" => serde_json::json!(info.word_length),
+        "max_guesses" => serde_json::json!(info.max_guesses),
+        "boards" => serde_json::json!(info.boards),
+        "random" => serde_json::json!(info.is_random),
+        "weighted" => serde_json::json!(info.is_weighted),
+        "difficult" => serde_json::json!(info.is_difficult),
+        "strict" => serde_json::json!(info.is_strict),
+        "adversarial" => serde_json::json!(info.is_adversarial),
+        "stats" => serde_json::json!(info.is_stats),
+        "share" => serde_json::json!(info.is_share),
+        "day" => serde_json::json!(info.day),
+        "seed" => serde_json::json!(info.seed),
+        "epoch" => serde_json::json!(info.epoch),
+        "lang" => serde_json::json!(info.lang),
+        "solver" => serde_json::json!(info.solver),
+        "final_set" => serde_json::json!(info.final_path),
+        "acceptable_set" => serde_json::json!(info.acceptable_path),
+        "state" => serde_json::json!(info.state_path),
+        "pattern_cache" => serde_json::json!(info.pattern_cache_path),
+        _ => serde_json::json!(word_to_guess),
+    }
+}
+
 /// Receive info, print statistics of guesses
+/// If rounds were played under different guess limits, add a line for each limit
 pub fn stats_to_string(info: &mut Info) ->String {
     let mut succeed_rounds: f64 = 0.0;
     let mut succeed_total_guess_times: f64 = 0.0;
-    let mut word_guessed_freq: Vec<(String, i32)> = vec![];
+    //(max guesses, succeeded, failed, total guess times of succeeded)
+    let mut limit_stats: Vec<(usize, i32, i32, usize)> = vec![];
     let mut stats:String;
     for temp in &info.state.games {
-        if temp.guesses.contains(&temp.answer) {
+        let is_success = temp.is_success();
+        if is_success {
             succeed_rounds += 1.0;
             succeed_total_guess_times += temp.guesses.len() as f64;
         }
-        for guess in &temp.guesses {
-            add_word_to_freq_list(&mut word_guessed_freq, &guess);
+        match limit_stats.iter_mut().find(|x| x.0 == temp.max_guesses) {
+            Some(limit) => {
+                if is_success {
+                    limit.1 += 1;
+                    limit.3 += temp.guesses.len();
+                } else {
+                    limit.2 += 1;
+                }
+            }
+            None => {
+                if is_success {
+                    limit_stats.push((temp.max_guesses, 1, 0, temp.guesses.len()));
+                } else {
+                    limit_stats.push((temp.max_guesses, 0, 1, 0));
+                }
+            }
         }
     }
     let average = if succeed_rounds != 0.0 {
         succeed_total_guess_times / succeed_rounds
     } else { 0.00 };
     stats=format!("{:.0} {} {:.2}\n", succeed_rounds, info.state.total_rounds - succeed_rounds as i32, average);
+    let top_words: Vec<String> = top_guessed_words(&info.state.games, 5).iter()
+        .map(|temp| format!("{} {}", to_upper_word(&temp.0), temp.1)).collect();
+    stats += &top_words.join(" ");
+    if limit_stats.len() > 1 {
+        limit_stats.sort();
+        for limit in &limit_stats {
+            let average = if limit.1 != 0 {
+                limit.3 as f64 / limit.1 as f64
+            } else { 0.00 };
+            stats += &format!("\n{}: {} {} {:.2}", limit.0, limit.1, limit.2, average);
+        }
+    }
+    stats
+}
+
+/// Receive games, return at most count words guessed most and their times,
+/// words guessed the same times in alphabet order
+pub fn top_guessed_words(games: &Vec<Game>, count: usize) -> Vec<(String, i32)> {
+    let mut word_guessed_freq: Vec<(String, i32)> = vec![];
+    for game in games {
+        for guess in &game.guesses {
+            add_word_to_freq_list(&mut word_guessed_freq, guess);
+        }
+    }
     word_guessed_freq.sort_by(|a, b| match b.1.cmp(&a.1) {
         Ordering::Greater | Ordering::Less => b.1.cmp(&a.1),
         Ordering::Equal => a.0.cmp(&b.0)
     });
-    let show_limit: i32 = if word_guessed_freq.len() < 5 {
-        word_guessed_freq.len() as i32
-    } else { 5 };
-    let mut i = 1;
-    for temp in &word_guessed_freq {
-        if i == show_limit {
-            stats+=&format!("{} {}", temp.0.to_ascii_uppercase(), temp.1);
-            break;
+    word_guessed_freq.truncate(count);
+    word_guessed_freq
+}
+
+/// Receive info, return the statistics of stats_to_string and Statistics as a JSON object
+pub fn stats_to_json(info: &Info) -> serde_json::Value {
+    let stats = Statistics::from_games(&info.state.games, info.max_guesses);
+    let won_guesses: usize = info.state.games.iter().filter(|x| x.is_success()).map(|x| x.guesses.len()).sum();
+    let average = if stats.wins != 0 { won_guesses as f64 / stats.wins as f64 } else { 0.0 };
+    let top_words: Vec<serde_json::Value> = top_guessed_words(&info.state.games, 5).iter()
+        .map(|(word, count)| serde_json::json!({ "word": word.to_lowercase(), "count": count }))
+        .collect();
+    serde_json::json!({
+        "played": stats.played,
+        "wins": stats.wins,
+        "losses": stats.failures,
+        "win_percentage": stats.win_percentage(),
+        "current_streak": stats.current_streak,
+        "max_streak": stats.max_streak,
+        "average_guesses": average,
+        "distribution": stats.distribution,
+        "top_words": top_words,
+    })
+}
+
+/// Win rate, streaks and distribution of guess times of played games
+pub struct Statistics {
+    pub played: usize,
+    pub wins: usize,
+    pub current_streak: usize,
+    pub max_streak: usize,
+    /// Number of games won with 1, 2, ... guesses
+    pub distribution: Vec<usize>,
+    pub failures: usize,
+}
+
+impl Statistics {
+    /// Receive games in the order they were played, and the guess limit for the length of distribution
+    pub fn from_games(games: &Vec<Game>, max_guesses: usize) -> Statistics {
+        let mut stats = Statistics {
+            played: games.len(),
+            wins: 0,
+            current_streak: 0,
+            max_streak: 0,
+            distribution: vec![0; max_guesses],
+            failures: 0,
+        };
+        for game in games {
+            if game.is_success() {
+                stats.wins += 1;
+                stats.current_streak += 1;
+                stats.max_streak = stats.max_streak.max(stats.current_streak);
+                if stats.distribution.len() < game.guesses.len() {
+                    stats.distribution.resize(game.guesses.len(), 0);
+                }
+                stats.distribution[game.guesses.len() - 1] += 1;
+            } else {
+                stats.failures += 1;
+                stats.current_streak = 0;
+            }
         }
-        stats+=&format!("{} {} ", temp.0.to_ascii_uppercase(), temp.1);
-        i += 1;
+        stats
     }
-    stats
+
+    /// Percentage of games won, 0 if no game played
+    pub fn win_percentage(&self) -> f64 {
+        if self.played == 0 { 0.0 } else { self.wins as f64 * 100.0 / self.played as f64 }
+    }
+
 }
 
-/// Receives a vector of 26 color strings and print
-/// If in tty, print letters, else just print color
-pub fn print_alphabet(alphabet_color: &Vec<Color>) {
-    let is_tty = atty::is(atty::Stream::Stdout);
-    if is_tty {
-        for i in 0..26 {
-            print!("{}",
-                   match alphabet_color[i] {
-                       Color::Y => console::style(ALPHABET[i].to_ascii_uppercase()).yellow().bold(),
-                       Color::R => console::style(ALPHABET[i].to_ascii_uppercase()).white().bold(),
-                       Color::G => console::style(ALPHABET[i].to_ascii_uppercase()).green().bold(),
-                       Color::X => console::style(ALPHABET[i].to_ascii_uppercase()).black().bright()
-                   }
-            )
+impl fmt::Display for Statistics {
+    /// A line of win rate and streaks, then a bar for each number of guesses and failure
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Played {} Win% {:.0} Current streak {} Max streak {}",
+               self.played, self.win_percentage(), self.current_streak, self.max_streak)?;
+        let most = self.distribution.iter().chain([self.failures].iter()).max().copied().unwrap_or(0).max(1);
+        let rows = self.distribution.iter().enumerate()
+            .map(|(i, count)| ((i + 1).to_string(), *count))
+            .chain([("X".to_string(), self.failures)]);
+        for (label, count) in rows {
+            //at least one block for a number that happened
+            let width = (count * 20).div_ceil(most);
+            write!(f, "\n{} {} {}", label, "█".repeat(width), count)?;
         }
-    } else {
-        for i in alphabet_color {
-            print!("{}", i.to_string());
+        Ok(())
+    }
+}
+
+/// Receive the state of a benchmark, print the distribution of guess times, failed words and worst words
+pub fn bench_to_string(state: &State) -> String {
+    let mut succeed_rounds = 0;
+    let mut succeed_total_guess_times = 0;
+    let mut max_guesses = 0;
+    let mut distribution: Vec<i32> = vec![];
+    let mut failed_words: Vec<String> = vec![];
+    for game in &state.games {
+        max_guesses = max_guesses.max(game.max_guesses);
+        if game.is_success() {
+            succeed_rounds += 1;
+            succeed_total_guess_times += game.guesses.len();
+            if distribution.len() < game.guesses.len() {
+                distribution.resize(game.guesses.len(), 0);
+            }
+            distribution[game.guesses.len() - 1] += 1;
+        } else {
+            failed_words.push(game.answer.clone());
         }
     }
-    println!();
+    distribution.resize(max_guesses.max(distribution.len()), 0);
+    let average = if succeed_rounds != 0 {
+        succeed_total_guess_times as f64 / succeed_rounds as f64
+    } else { 0.00 };
+    let mut stats = format!("{} {} {:.2}\n", succeed_rounds, state.games.len() - succeed_rounds, average);
+    let distribution: Vec<String> = distribution.iter().enumerate()
+        .map(|(i, count)| format!("{} {}", i + 1, count)).collect();
+    stats += &distribution.join(" ");
+    stats += "\n";
+    stats += &failed_words.join(" ");
+    //failed words first, then words need most guesses
+    let mut worst: Vec<&Game> = state.games.iter().collect();
+    worst.sort_by(|a, b| {
+        a.is_success().cmp(&b.is_success())
+            .then(b.guesses.len().cmp(&a.guesses.len()))
+            .then(a.answer.cmp(&b.answer))
+    });
+    let worst: Vec<String> = worst.iter().take(5).map(|game| if game.is_success() {
+        format!("{} {}", game.answer, game.guesses.len())
+    } else {
+        format!("{} X", game.answer)
+    }).collect();
+    stats += "\n";
+    stats += &worst.join(" ");
+    stats
 }
 
 /// add a word to a frequency list recorded by tuple
@@ -636,15 +1555,58 @@ pub fn add_word_to_freq_list(freq_list: &mut Vec<(String, i32)>, word: &String)
 }
 
 /// Receive a string of path, update a set of word form the file in the path
-pub fn set_from_path(path: &String, set: &mut Vec<String>) {
-    if let Ok(whole_string) = fs::read_to_string(&path) {
-        set.clear();
-        for temp in whole_string.split_terminator("\n")
-        {
-            set.push(temp.to_string().to_ascii_lowercase());
-        }
-        set.sort();
+/// Comments, blank lines and duplicates are skipped
+/// Return the frequency of each word, or Error if the file cannot be read or a word is wrong
+pub fn set_from_path(path: &String, set: &mut Vec<String>, word_length: usize) -> Result<Vec<u64>, Error> {
+    let list = lists::read_word_list(path, word_length)?;
+    if let Some(problem) = list.problems.iter().find(|x| x.is_error) {
+        return Err(Error::WordList { path: path.clone(), line: problem.line, message: problem.message.clone() });
+    }
+    *set = list.words;
+    Ok(list.weights)
+}
+
+/// Receive word lists, return the letters used in them in order of code points
+/// Lists of only ASCII letters use the whole ALPHABET, so the keyboard keeps every English letter
+pub fn alphabet_of(lists: &[&Vec<String>]) -> Vec<char> {
+    let mut alphabet: Vec<char> = lists.iter().flat_map(|list| list.iter().flat_map(|word| word.chars())).collect();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    if alphabet.iter().all(|x| x.is_ascii_lowercase()) {
+        return ALPHABET.to_vec();
     }
+    alphabet
+}
+
+/// Uppercase a word letter by letter to print or save it
+/// A letter without a single uppercase letter, like ß, is kept, so the word keeps its length
+pub fn to_upper_word(word: &str) -> String {
+    word.chars().map(|letter| {
+        let mut upper = letter.to_uppercase();
+        match (upper.next(), upper.next()) {
+            (Some(single), None) => single,
+            _ => letter,
+        }
+    }).collect()
+}
+
+/// The language pack of the code, or Error naming the packs available
+fn language_pack(name: &str) -> Result<&'static lang::LanguagePack, Error> {
+    lang::pack(name).ok_or_else(|| Error::InvalidArgs(format!(
+        "unknown language '{}', available: {}", name, lang::names().join(", "))))
+}
+
+/// Receive an alphabet, return it as three rows of keys in the same order
+pub fn keyboard_of(alphabet: &[char]) -> Vec<String> {
+    alphabet.chunks(alphabet.len().div_ceil(3).max(1)).map(|row| row.iter().collect()).collect()
+}
+
+/// Receive the letters of keyboard and their colors, return a JSON object of the color of each letter
+pub fn keyboard_to_json(alphabet: &[char], alphabet_color: &[Color]) -> serde_json::Value {
+    let keyboard: serde_json::Map<String, serde_json::Value> = alphabet.iter().zip(alphabet_color)
+        .map(|(letter, color)| (letter.to_string(), serde_json::json!(color.to_string())))
+        .collect();
+    serde_json::Value::Object(keyboard)
 }
 
 /// Convert a vector of color to string
@@ -659,40 +1621,35 @@ pub fn color_vec_to_string(vec: &Vec<Color>) -> String {
 ///Receive a word list, a word guessed, and a guess result, return a word list contains all words in the former list
 ///that matches the result
 pub fn get_new_hint_list(hint_list: &Vec<String>, guess_word: &String, word_result: &Vec<Color>) -> Vec<String> {
-    let mut new_hint: Vec<String> = vec![];
-    for acc in hint_list {
-        if color_vec_to_string(&calculate_color(acc, guess_word)) ==
-            color_vec_to_string(word_result) {
-            new_hint.push(acc.clone());
-        }
-    }
-    new_hint
+    solver::filter_candidates(hint_list, guess_word, pattern::encode_colors(word_result), None)
 }
 
-/// Use info and round info to read a guess and check it return the result of guess string
-pub fn get_checked_guess(info: &&mut Info, round_info: &mut RoundInfo) -> Result<String, Error> {
-    let mut guess_word = String::new();
+/// Use acceptable set and round info to check a guess, return the result of guess string
+pub fn get_checked_guess(
+    guess_word: &str,
+    acceptable_set: &Vec<String>,
+    is_difficult: bool,
+    round_info: &RoundInfo) -> Result<String, Error> {
+    let guess_word = guess_word.to_string();
     let mut is_in_acc = false;
-    io::stdin().read_line(&mut guess_word).expect("cannot read");
-    guess_word.pop();
-    for acceptable in &info.acceptable_set {
-        if guess_word == acceptable.to_string() {
+    for acceptable in acceptable_set {
+        if guess_word == *acceptable {
             is_in_acc = true;
         }
     }
     if !is_in_acc {
-        return Err(Error::InvalidWord);
+        return Err(Error::InvalidWord("not in the acceptable set"));
     }
-    if info.is_difficult {
+    if is_difficult {
         for temp in round_info.already_guessed_position.iter() {
             if guess_word.chars().nth(temp.0 as usize).unwrap() != temp.1 {
-                return Err(Error::InvalidWord);
+                return Err(Error::InvalidWord("a green letter is moved"));
             }
         }//letters already correct cannot change
-        for i in 0..26 {
+        for i in 0..round_info.alphabet.len() {
             if let Color::Y = round_info.alphabet_color[i] {
-                if !guess_word.contains(ALPHABET[i]) {
-                    return Err(Error::InvalidWord);
+                if !guess_word.contains(round_info.alphabet[i]) {
+                    return Err(Error::InvalidWord("a yellow letter is not used"));
                 }
             }
         }
@@ -701,32 +1658,15 @@ pub fn get_checked_guess(info: &&mut Info, round_info: &mut RoundInfo) -> Result
     Ok(guess_word)
 }
 
-/// Print 3 recommend word from the possible list
-pub fn recommend_from_hint_list(list: &Vec<String>) {
-    let mut temp_list = list.clone();
-    temp_list.sort_by(|a, b|
-        get_grade_one_depth(list, &b).cmp(&get_grade_one_depth(list, &a))
-    );
-    let mut iter = temp_list.iter();
-    let mut count = 0;
-    while let Some(t) = iter.next() {
-        print!("{} ", t);
-        count += 1;
-        if count == 2 { break; }
-    }
-    println!();
+/// Check a guess with every deduction from former guesses and their colors in strict mode
+/// The guess must keep G letters, move Y letters, use letters at least as many times as known,
+/// and not use letters proven absent, which means it could still be the answer
+pub fn is_consistent_guess(guess_word: &str, history: &[(String, Vec<Color>)]) -> bool {
+    history.iter().all(|(former_guess, word_result)|
+        pattern::get_pattern(guess_word, former_guess) == pattern::encode_colors(word_result))
 }
 
-/// Get the recommend grade of a word
-pub fn get_grade_one_depth(list: &Vec<String>, next_guess: &String) -> i32 {
-    let mut quantity_list: Vec<i32> = vec![];
-    for possible_answer in list.iter() {
-        let result = calculate_color(&possible_answer, &next_guess);
-        let acc_quantity = get_new_hint_list(&list, next_guess, &result).len() as i32;
-        quantity_list.push(acc_quantity);
-    }
-    let average: i32 = quantity_list.iter().sum();
-    average
+/// Return 2 recommend words from the possible list, graded by entropy
+pub fn recommend_from_hint_list(list: &Vec<String>, table: Option<&PatternTable>) -> Vec<String> {
+    solver::rank_guesses(list, list, table).into_iter().take(2).map(|x| x.word).collect()
 }
-
-
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+use serde_json::{json, Value};
+use crate::{color_vec_to_string, config, keyboard_to_json, Error, GameSession, Info};
+
+/// Largest body of a request, a game only needs a few options
+const MAX_BODY_LENS: usize = 64 * 1024;
+
+/// Largest request line and headers together
+const MAX_HEAD_LENS: usize = 8 * 1024;
+
+/// Most games kept at the same time, finished games and then the oldest ones make room for new games
+const MAX_GAMES: usize = 1000;
+
+/// A connection that sends nothing for this long is closed
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Games by id, shared by every connection
+struct Games {
+    next_id: u64,
+    sessions: HashMap<u64, GameSession>,
+}
+
+impl Games {
+    /// Drop finished games when there are too many, and the oldest game if every game is still played
+    fn make_room(&mut self) {
+        if self.sessions.len() < MAX_GAMES {
+            return;
+        }
+        self.sessions.retain(|_, session| !session.is_over());
+        if self.sessions.len() >= MAX_GAMES {
+            if let Some(oldest) = self.sessions.keys().min().copied() {
+                self.sessions.remove(&oldest);
+            }
+        }
+    }
+}
+
+type SharedGames = Arc<Mutex<Games>>;
+
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+/// Status and JSON body of an answer
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response { status, body: json!({ "error": message }) }
+    }
+}
+
+/// Accept connections forever and answer one request on each of them, in its own thread
+/// `POST /games` creates a game with options in the body like a config file, such as {"seed": 1, "day": 2}
+/// `POST /games/{id}/guesses` with {"guess": "crane"} guesses, and `GET /games/{id}` shows a game
+/// Every game starts from info, analyzed from the command line of the server, with the options of the request applied
+pub fn serve(listener: TcpListener, info: Arc<Info>) {
+    let games: SharedGames = Arc::new(Mutex::new(Games { next_id: 1, sessions: HashMap::new() }));
+    for stream in listener.incoming() {
+        //a failed connection does not stop the others
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        let games = games.clone();
+        let info = info.clone();
+        thread::spawn(move || {
+            let _ = handle_connection(stream, &info, &games);
+        });
+    }
+}
+
+fn handle_connection(stream: TcpStream, info: &Info, games: &SharedGames) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let response = match read_request(&mut reader)? {
+        Ok(request) => route(&request, info, games),
+        Err(response) => response,
+    };
+    let body = response.body.to_string();
+    writer.write_all(format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status, reason(response.status), body.len(), body).as_bytes())?;
+    //closing with a request not read to the end would reset the connection before the client reads the response
+    writer.shutdown(Shutdown::Write)?;
+    io::copy(&mut reader.take(MAX_BODY_LENS as u64), &mut io::sink())?;
+    Ok(())
+}
+
+/// Read the request line, headers and body
+/// Return the request, or the response to a malformed one
+fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let too_large = || Response::error(431, "request line and headers are too large");
+    let mut head_left = MAX_HEAD_LENS;
+    let line = match read_head_line(reader, &mut head_left)? {
+        Some(line) => line,
+        None => return Ok(Err(too_large())),
+    };
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() != 3 {
+        return Ok(Err(Response::error(400, "malformed request line")));
+    }
+    let mut content_length = 0;
+    loop {
+        let header = match read_head_line(reader, &mut head_left)? {
+            Some(header) => header,
+            None => return Ok(Err(too_large())),
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                match value.trim().parse() {
+                    Ok(length) => content_length = length,
+                    Err(_) => return Ok(Err(Response::error(400, "invalid Content-Length"))),
+                }
+            }
+        }
+    }
+    if content_length > MAX_BODY_LENS {
+        return Ok(Err(Response::error(413, "request body is too large")));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    match String::from_utf8(body) {
+        Ok(body) => Ok(Ok(Request { method: parts[0].to_string(), path: parts[1].to_string(), body })),
+        Err(_) => Ok(Err(Response::error(400, "request body is not UTF-8"))),
+    }
+}
+
+/// Read a line of the request line and headers, what it reads is taken from the bytes left for them
+/// Return None if the line does not fit, an empty line at the end of input
+fn read_head_line(reader: &mut impl BufRead, left: &mut usize) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    let read = (&mut *reader).take(*left as u64).read_line(&mut line)?;
+    if read == *left && !line.ends_with('\n') {
+        return Ok(None);
+    }
+    *left -= read;
+    Ok(Some(line))
+}
+
+fn route(request: &Request, info: &Info, games: &SharedGames) -> Response {
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["games"]) => create_game(&request.body, info, games),
+        ("POST", ["games", id, "guesses"]) => with_game(games, id, |id, session| guess(id, session, &request.body)),
+        ("GET", ["games", id]) => with_game(games, id, |id, session| Response { status: 200, body: game_view(id, session) }),
+        (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "guesses"]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn lock(games: &SharedGames) -> MutexGuard<'_, Games> {
+    games.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Start a new game from the info of the server with the options in body
+/// Without a day, a game takes the answer of the day of its id, so every game is different
+fn create_game(body: &str, server_info: &Info, games: &SharedGames) -> Response {
+    let mut options = match config::read_request(body) {
+        Ok(options) => options,
+        Err(err) => return Response::error(400, &err.to_string()),
+    };
+    let id = {
+        let mut games = lock(games);
+        games.next_id += 1;
+        games.next_id - 1
+    };
+    if options.get("day").is_none() {
+        options["day"] = json!(id);
+    }
+    let mut info = server_info.clone();
+    if let Err(err) = info.apply_request(&options) {
+        return Response::error(400, &err.to_string());
+    }
+    let answer = info.next_random_answer();
+    let session = GameSession::new(&info, &answer);
+    let body = game_view(id, &session);
+    let mut games = lock(games);
+    games.make_room();
+    games.sessions.insert(id, session);
+    Response { status: 201, body }
+}
+
+/// Run f with the game of the id, or answer 404 if there is no such game
+fn with_game(games: &SharedGames, id: &str, f: impl FnOnce(u64, &mut GameSession) -> Response) -> Response {
+    let mut games = lock(games);
+    match id.parse::<u64>().ok().and_then(|id| games.sessions.get_mut(&id).map(|session| (id, session))) {
+        Some((id, session)) => f(id, session),
+        None => Response::error(404, &format!("no game '{}'", id)),
+    }
+}
+
+/// Apply the guess in body, answer the game with the pattern of the guess
+fn guess(id: u64, session: &mut GameSession, body: &str) -> Response {
+    let guess = serde_json::from_str::<Value>(body).ok()
+        .and_then(|x| x.get("guess").and_then(|guess| guess.as_str()).map(|guess| guess.to_lowercase()));
+    let guess = match guess {
+        Some(guess) => guess,
+        None => return Response::error(400, "body should be like {\"guess\": \"crane\"}"),
+    };
+    match session.submit_guess(&guess) {
+        Ok(outcome) => {
+            let mut view = game_view(id, session);
+            view["guess"] = json!(outcome.guess);
+            view["pattern"] = json!(color_vec_to_string(&outcome.colors));
+            view["correct"] = json!(outcome.is_correct);
+            Response { status: 200, body: view }
+        }
+        Err(Error::GameOver) => Response::error(409, "the game is over"),
+        Err(Error::InvalidWord(reason)) => Response { status: 422, body: json!({ "error": "INVALID", "reason": reason }) },
+        Err(err) => Response::error(422, &err.to_string()),
+    }
+}
+
+/// Every guess of a game with its pattern, the color of each letter, guesses left,
+/// and the answer once the game is over
+fn game_view(id: u64, session: &GameSession) -> Value {
+    let guesses: Vec<Value> = session.history().iter()
+        .map(|(guess, colors)| json!({ "guess": guess, "pattern": color_vec_to_string(colors) }))
+        .collect();
+    let status = if session.is_success() {
+        "won"
+    } else if session.is_over() {
+        "lost"
+    } else {
+        "playing"
+    };
+    let mut view = json!({
+        "id": id,
+        "status": status,
+        "word_length": session.answer().chars().count(),
+        "remaining_guesses": session.guesses_left(),
+        "guesses": guesses,
+        "keyboard": keyboard_to_json(session.alphabet(), session.keyboard()),
+    });
+    if session.is_over() {
+        view["answer"] = json!(session.answer());
+    }
+    view
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        422 => "Unprocessable Entity",
+        _ => "Error",
+    }
+}
@@ -0,0 +1,50 @@
+use crate::builtin_words::{ACCEPTABLE, FINAL};
+use crate::ALPHABET;
+
+#[cfg(feature = "lang-de")]
+mod de;
+#[cfg(feature = "lang-fr")]
+mod fr;
+
+/// Language of the built-in lists when --lang is not given
+pub const DEFAULT_LANG: &str = "en";
+
+/// Built-in words of a language, with the letters and keyboard layout to show them
+pub struct LanguagePack {
+    /// Code given to --lang
+    pub name: &'static str,
+    pub final_words: &'static [&'static str],
+    /// Sorted, and contains every word of final_words
+    pub acceptable_words: &'static [&'static str],
+    /// Letters of the language in order of code points
+    pub alphabet: &'static [char],
+    /// Rows of the keyboard from top to bottom
+    pub keyboard: &'static [&'static str],
+}
+
+const ENGLISH: LanguagePack = LanguagePack {
+    name: DEFAULT_LANG,
+    final_words: FINAL,
+    acceptable_words: ACCEPTABLE,
+    alphabet: ALPHABET,
+    keyboard: &["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+};
+
+/// Every pack compiled in, other than English each one needs the feature lang-NAME
+const PACKS: &[LanguagePack] = &[
+    ENGLISH,
+    #[cfg(feature = "lang-de")]
+    de::PACK,
+    #[cfg(feature = "lang-fr")]
+    fr::PACK,
+];
+
+/// The pack of the language code, None if it is unknown or not compiled in
+pub fn pack(name: &str) -> Option<&'static LanguagePack> {
+    PACKS.iter().find(|x| x.name == name)
+}
+
+/// Codes of every pack compiled in
+pub fn names() -> Vec<&'static str> {
+    PACKS.iter().map(|x| x.name).collect()
+}
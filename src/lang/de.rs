@@ -0,0 +1,49 @@
+use super::LanguagePack;
+
+pub const PACK: LanguagePack = LanguagePack {
+    name: "de",
+    final_words: FINAL,
+    acceptable_words: ACCEPTABLE,
+    alphabet: &['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n',
+        'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'ß', 'ä', 'ö', 'ü'],
+    keyboard: &["qwertzuiopü", "asdfghjklöä", "yxcvbnmß"],
+};
+
+/// German words that may be the answer
+pub const FINAL: &[&str] = &[
+    "abend", "adler", "alarm", "angst", "apfel", "asche", "atlas", "bauch", "bauer", "biene",
+    "birne", "blatt", "blick", "blitz", "blume", "boden", "bogen", "brand", "brief", "brust",
+    "dampf", "decke", "degen", "draht", "eimer", "eisen", "engel", "ernte", "essig", "fabel",
+    "faden", "farbe", "feder", "fleiß", "fluss", "flöte", "frage", "funke", "gabel", "geist",
+    "glanz", "gleis", "größe", "grüße", "hafen", "hagel", "halle", "hebel", "heide", "honig",
+    "härte", "hütte", "insel", "kabel", "kamin", "kerze", "kette", "kiste", "klang", "kleid",
+    "knopf", "kraft", "kranz", "krieg", "kugel", "küche", "laden", "lampe", "leder", "licht",
+    "liebe", "macht", "mauer", "menge", "milch", "musik", "mühle", "nabel", "nadel", "nebel",
+    "onkel", "opfer", "paket", "pferd", "pilot", "platz", "preis", "probe", "punkt", "quarz",
+    "rasen", "recht", "regen", "reise", "rinde", "schaf", "sonne", "spiel", "stadt", "stall",
+    "staub", "stein", "stern", "stirn", "stock", "stuhl", "sturm", "säule", "tafel", "tanne",
+    "tante", "tasse", "teich", "thron", "tisch", "traum", "treue", "vogel", "waage", "wagen",
+    "wange", "welle", "wolke", "würde", "zange", "zeuge",
+];
+
+/// German words allowed to guess
+pub const ACCEPTABLE: &[&str] = &[
+    "abend", "adler", "alarm", "angst", "apfel", "asche", "atlas", "bauch", "bauer", "biene",
+    "birne", "blass", "blatt", "blick", "blitz", "blume", "boden", "bogen", "brand", "breit",
+    "brief", "brust", "bäche", "bären", "bühne", "dampf", "decke", "degen", "dicht", "draht",
+    "eimer", "eisen", "engel", "ernte", "essen", "essig", "fabel", "faden", "farbe", "feder",
+    "fleiß", "fluss", "flöte", "frage", "frech", "fromm", "funke", "fügen", "gabel", "geben",
+    "gehen", "geist", "glanz", "gleis", "größe", "grüße", "haben", "hafen", "hagel", "halle",
+    "hebel", "heide", "holen", "honig", "härte", "hölle", "hüfte", "hütte", "insel", "kabel",
+    "kamin", "kerze", "kette", "kiste", "klang", "kleid", "klein", "knopf", "kraft", "krank",
+    "kranz", "krieg", "kugel", "käfer", "küche", "küken", "laden", "lampe", "laute", "leben",
+    "leder", "legen", "leise", "lesen", "licht", "liebe", "löwen", "lügen", "macht", "mauer",
+    "menge", "milch", "musik", "mähen", "möhre", "mühle", "mütze", "nabel", "nadel", "nebel",
+    "nägel", "nähen", "onkel", "opfer", "paket", "pferd", "pilot", "platz", "preis", "probe",
+    "punkt", "quarz", "rasen", "recht", "reden", "regen", "reise", "rinde", "rufen", "räder",
+    "sagen", "sanft", "schaf", "schön", "sehen", "sonne", "spiel", "stadt", "stall", "stark",
+    "staub", "stein", "stern", "stirn", "stock", "stolz", "stuhl", "sturm", "sägen", "säule",
+    "säure", "tafel", "tanne", "tante", "tasse", "teich", "thron", "tisch", "traum", "treue",
+    "türen", "vogel", "vögel", "waage", "wagen", "wange", "welle", "wolke", "würde", "zange",
+    "zeuge", "zügel",
+];
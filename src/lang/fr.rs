@@ -0,0 +1,44 @@
+use super::LanguagePack;
+use crate::ALPHABET;
+
+pub const PACK: LanguagePack = LanguagePack {
+    name: "fr",
+    final_words: FINAL,
+    acceptable_words: ACCEPTABLE,
+    alphabet: ALPHABET,
+    keyboard: &["azertyuiop", "qsdfghjklm", "wxcvbn"],
+};
+
+/// French words that may be the answer, written without accents
+pub const FINAL: &[&str] = &[
+    "arbre", "avion", "bague", "balle", "barbe", "blanc", "calme", "carte", "chaud", "chien",
+    "chose", "clair", "coeur", "corps", "coude", "court", "crane", "danse", "doigt", "douce",
+    "drole", "ecole", "effet", "fable", "faute", "femme", "fleur", "force", "forme", "foule",
+    "frere", "froid", "fruit", "genou", "grand", "grave", "guide", "herbe", "heure", "hiver",
+    "homme", "image", "jaune", "jeune", "jouer", "juste", "lampe", "large", "libre", "livre",
+    "lourd", "lundi", "mardi", "masse", "merci", "monde", "neige", "noire", "nuage", "ombre",
+    "oncle", "orage", "outil", "parti", "pause", "peine", "perle", "petit", "piano", "place",
+    "plage", "plein", "pluie", "poche", "point", "poire", "porte", "poule", "radio", "reine",
+    "repas", "reste", "rever", "riche", "rouge", "route", "sable", "salle", "sauce", "signe",
+    "soeur", "somme", "sucre", "table", "tante", "tarte", "temps", "terre", "tigre", "titre",
+    "train", "usine", "vache", "valse", "vente", "verre", "ville", "vivre", "voile", "voler",
+    "zebre",
+];
+
+/// French words allowed to guess, written without accents
+pub const ACCEPTABLE: &[&str] = &[
+    "aimer", "aller", "arbre", "avion", "avoir", "bague", "balle", "barbe", "blanc", "boire",
+    "calme", "carte", "chaud", "chien", "chose", "clair", "coeur", "corps", "coude", "court",
+    "crane", "crier", "danse", "doigt", "douce", "drole", "durer", "ecole", "effet", "fable",
+    "faute", "femme", "finir", "fleur", "force", "forme", "foule", "frere", "froid", "fruit",
+    "gagne", "geler", "genou", "grand", "grave", "guide", "herbe", "heure", "hiver", "homme",
+    "image", "jaune", "jeter", "jeune", "jouer", "juste", "lampe", "large", "laver", "lever",
+    "libre", "livre", "lourd", "lundi", "mardi", "masse", "mener", "merci", "monde", "neige",
+    "noire", "noter", "nuage", "ombre", "oncle", "orage", "outil", "parti", "pause", "payer",
+    "peine", "perle", "peser", "petit", "piano", "place", "plage", "plein", "pluie", "poche",
+    "point", "poire", "porte", "poser", "poule", "radio", "rater", "reine", "repas",
+    "reste", "rever", "riche", "rouge", "route", "sable", "salle", "sauce", "semer", "signe",
+    "soeur", "somme", "sucre", "table", "tante", "tarte", "temps", "tenir", "terre", "tigre",
+    "titre", "train", "usine", "vache", "valse", "venir", "vente", "verre", "ville", "vivre",
+    "voile", "voler", "zebre",
+];
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::fs;
+use crate::Error;
+
+/// Something wrong in a line of a word list, line starts from 1
+/// A problem that is not an error, like a duplicate, is fixed when loading
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub line: usize,
+    pub message: String,
+    pub is_error: bool,
+}
+
+/// Words of a list normalized to lowercase, sorted and without duplicates, with the problems found
+pub struct WordList {
+    pub words: Vec<String>,
+    /// Frequency of each word in words, 1 for a word without one
+    pub weights: Vec<u64>,
+    /// The line of each word, for problems found later
+    pub lines: HashMap<String, usize>,
+    pub problems: Vec<Problem>,
+}
+
+/// Receive the content of a word list, one word each line, optionally followed by a tab and its frequency
+/// Blank lines and lines starting with # are skipped, spaces and \r around a word are trimmed
+/// A list without any word is an error
+pub fn parse_word_list(content: &str, word_length: usize) -> WordList {
+    let mut list = WordList { words: vec![], weights: vec![], lines: HashMap::new(), problems: vec![] };
+    let mut weighted_words: Vec<(String, u64)> = vec![];
+    //answers are drawn from the total, so it must fit in u64
+    let mut total_weight: u64 = 0;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let word = fields[0].to_lowercase();
+        let weight = match fields.get(1) {
+            None => Some(1),
+            Some(count) => count.parse::<u64>().ok().filter(|x| *x > 0),
+        };
+        let problem = if fields.len() > 2 {
+            Some((format!("'{}' should be a word and its frequency", line), true))
+        } else if weight.is_none() {
+            Some((format!("'{}' is not a frequency of '{}', it should be a positive number", fields[1], word), true))
+        } else if !word.chars().all(|x| x.is_alphabetic()) {
+            Some((format!("'{}' has a character that is not a letter", word), true))
+        } else if word.chars().count() != word_length {
+            Some((format!("'{}' is not of length {}", word, word_length), true))
+        } else if let Some(first) = list.lines.get(&word) {
+            Some((format!("'{}' is a duplicate of line {}", word, first), false))
+        } else if total_weight.checked_add(weight.unwrap_or(1)).is_none() {
+            Some((format!("the frequency of '{}' makes the total frequency too large", word), true))
+        } else {
+            None
+        };
+        match problem {
+            Some((message, is_error)) => list.problems.push(Problem { line: i + 1, message, is_error }),
+            None => {
+                total_weight += weight.unwrap_or(1);
+                list.lines.insert(word.clone(), i + 1);
+                weighted_words.push((word, weight.unwrap_or(1)));
+            }
+        }
+    }
+    //a round needs an answer to draw and words to accept, so an empty list is an error at its last line
+    if weighted_words.is_empty() {
+        list.problems.push(Problem { line: content.lines().count().max(1), message: "the list has no words".to_string(), is_error: true });
+    }
+    weighted_words.sort();
+    (list.words, list.weights) = weighted_words.into_iter().unzip();
+    list
+}
+
+/// Receive the path of a word list, return the words in it with the problems found
+/// Return a result with Error if the file cannot be read
+pub fn read_word_list(path: &str, word_length: usize) -> Result<WordList, Error> {
+    let content = fs::read_to_string(path).map_err(|source| Error::Io { path: path.to_string(), source })?;
+    Ok(parse_word_list(&content, word_length))
+}
+
+/// Receive the paths of final and acceptable lists, return every problem of them with the path of its list,
+/// including words of final list that are not acceptable
+/// Return a result with Error if a file cannot be read
+pub fn check_lists(final_path: &str, acceptable_path: &str, word_length: usize) -> Result<Vec<(String, Problem)>, Error> {
+    let final_list = read_word_list(final_path, word_length)?;
+    let acceptable_list = read_word_list(acceptable_path, word_length)?;
+    let mut final_problems = final_list.problems.clone();
+    for word in &final_list.words {
+        if !acceptable_list.lines.contains_key(word) {
+            final_problems.push(Problem {
+                line: final_list.lines[word],
+                message: format!("'{}' is not in the acceptable list", word),
+                is_error: true,
+            });
+        }
+    }
+    final_problems.sort_by_key(|x| x.line);
+    Ok(final_problems.into_iter().map(|x| (final_path.to_string(), x))
+        .chain(acceptable_list.problems.into_iter().map(|x| (acceptable_path.to_string(), x)))
+        .collect())
+}
@@ -1,16 +1,40 @@
-
 use func;
 
 use std::fs;
+use std::net::TcpListener;
+use std::sync::Arc;
 use console;
 use std::io::{self};
-use serde_json;
-use func::Info;
-
+use serde_json::{self, json, Value};
+use func::{to_upper_word, Color, Error, GameSession, Info, MultiSession};
+use func::args::Command;
+use func::config;
+use func::pattern::{encode_colors, Pattern};
+use func::solver::Solver;
 
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        if let Error::Usage(_) = err {
+            eprintln!("Try 'wordle --help' for more information.");
+        }
+        std::process::exit(exit_code(&err));
+    }
+}
 
+/// Exit code of an error, following sysexits.h
+fn exit_code(err: &Error) -> i32 {
+    match err {
+        Error::Usage(_) | Error::InvalidArgs(_) | Error::Parse { .. } => 64,
+        Error::WordList { .. } | Error::State { .. } => 65,
+        Error::Io { .. } => 74,
+        Error::Config { .. } => 78,
+        Error::AlreadyPlayed(_) | Error::InvalidWord(_) | Error::GameOver => 1,
+    }
+}
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// Analyze args, then play or run the subcommand
+fn run() -> Result<(), Error> {
     let is_tty = atty::is(atty::Stream::Stdout);
 
     let mut word_to_guess = String::new();
@@ -18,42 +42,125 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut info = Info::new();
     let mut is_continue_playing = true;
     let args:Vec<String>=std::env::args().collect();
-    func::info_analyze(&mut word_to_guess, &mut info,&args).expect("args error");
-    if is_tty {
+    let configs = config::discover();
+    func::info_analyze(&mut word_to_guess, &mut info, &args, &configs)?;
+    match info.command {
+        Command::Play => {}
+        Command::Help => {
+            println!("{}", func::args::help());
+            return Ok(());
+        }
+        Command::Version => {
+            println!("{}", func::args::version());
+            return Ok(());
+        }
+        Command::Bench => {
+            println!("{}", func::bench_to_string(&func::solver::bench(&info)));
+            return Ok(());
+        }
+        Command::Replay => {
+            replay(&info);
+            return Ok(());
+        }
+        Command::Stats => {
+            if info.is_jsonl {
+                print_stats_event(&info);
+                return Ok(());
+            }
+            if is_tty {
+                println!("{}", func::Statistics::from_games(&info.state.games, info.max_guesses));
+            }
+            println!("{}", func::stats_to_string(&mut info));
+            return Ok(());
+        }
+        Command::Solve => return solve(&info),
+        Command::Lists => {
+            let problems = info.check_lists()?;
+            for (path, problem) in &problems {
+                let level = if problem.is_error { "error" } else { "warning" };
+                println!("{}:{}: {}: {}", path, problem.line, level, problem.message);
+            }
+            //the first error fails like loading the list would
+            if let Some((path, problem)) = problems.iter().find(|x| x.1.is_error) {
+                return Err(Error::WordList { path: path.clone(), line: problem.line, message: problem.message.clone() });
+            }
+            println!("OK");
+            return Ok(());
+        }
+        Command::Config => {
+            println!("{}", func::config_to_string(&info, &word_to_guess));
+            return Ok(());
+        }
+        Command::Serve => {
+            let address = format!("0.0.0.0:{}", info.port);
+            let listener = TcpListener::bind(&address)
+                .map_err(|source| Error::Io { path: address.clone(), source })?;
+            if let Ok(local) = listener.local_addr() {
+                println!("Listening on {}", local);
+            }
+            if info.is_http {
+                func::http::serve(listener, Arc::new(info));
+            } else {
+                func::server::serve(listener, Arc::new(info));
+            }
+            return Ok(());
+        }
+    }
+    if is_tty && !info.is_jsonl {
         println!(
             "{}", console::style("Game Starts!").bold().blink().blue()
         );
     }
     //play several times
     while is_continue_playing {
-        match func::guess_round(&mut word_to_guess, &mut info) {
-            Ok(()) => {}
-            Err(err) => println!("{}", err.to_string())
+        if info.boards > 1 {
+            guess_multi_round(&mut info)?;
+        } else {
+            guess_round(&mut word_to_guess, &mut info)?;
+        }
+        if info.is_share {
+            if let Some(game) = info.state.games.last() {
+                if info.is_jsonl {
+                    print_event(json!({ "event": "share", "text": func::share_to_string(game, &info) }));
+                } else {
+                    println!("{}", func::share_to_string(game, &info));
+                }
+            }
         }
         //if in --word info, break
         if info.is_word_specified {
             break;
         }
-        if info.is_stats {
+        if info.is_stats && info.is_jsonl {
+            print_stats_event(&info);
+        } else if info.is_stats {
+            if is_tty {
+                println!("{}", func::Statistics::from_games(&info.state.games, info.max_guesses));
+            }
             println!("{}",func::stats_to_string(&mut info));
         }
         //judge if continue
         let mut choice: Option<bool> = None;
         while let None = choice {
-            if is_tty {
+            if is_tty && !info.is_jsonl {
                 println!("Do you want to continue? Type [Y]or[N] to tell me");
             }
             let mut whether_another: String = String::new();
             match io::stdin().read_line(&mut whether_another) {
                 Ok(n) => if n == 0 { choice = Some(false) }
                 //stop when read EOF
-                Err(err) => return Err(Box::from(err))
+                Err(source) => return Err(Error::Io { path: "stdin".to_string(), source })
             }
             whether_another.pop();
             match &whether_another[..] {
                 "Y" => choice = Some(true),
                 "N" => choice = Some(false),
                 "" => choice = Some(false),
+                _ if info.is_jsonl => print_event(json!({
+                    "event": "invalid",
+                    "input": whether_another,
+                    "reason": "answer Y to continue or N to stop",
+                })),
                 _ => {
                     println!("INVALID")
                 }
@@ -62,12 +169,404 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         is_continue_playing = choice.unwrap();
     }
     //update state file
-    if info.is_stated && info.is_random {
+    if info.is_stated && (info.is_random || info.is_adversarial || !info.date.is_empty()) {
         let state_string = serde_json::to_string_pretty(&info.state).unwrap();
-        fs::write(info.state_path, state_string).unwrap();
+        fs::write(&info.state_path, state_string)
+            .map_err(|source| Error::Io { path: info.state_path.clone(), source })?;
     }
     Ok(())
 }
 
+/// Receives the word to guess this round, then plays a round of game on stdin and stdout
+/// Return a result with Error
+fn guess_round(word_to_guess: &mut String, info: &mut Info) -> Result<(), Error> {
+    let is_tty = atty::is(atty::Stream::Stdout) && !info.is_jsonl;
 
+    if info.is_random {
+        *word_to_guess = info.next_random_answer();
+    } else if !info.is_word_specified && !info.is_adversarial {
+        word_to_guess.clear();
+        if is_tty {
+            println!("Default mode, please input the answer you set ");
+        }
+        io::stdin().read_line(word_to_guess)
+            .map_err(|source| Error::Io { path: "stdin".to_string(), source })?;
+        word_to_guess.pop();
+        if !info.final_set.contains(word_to_guess) {
+            return Err(Error::InvalidArgs(format!("'{}' is not in the final set", word_to_guess)));
+        }
+    }
+    if is_tty {
+        println!("This is round {}, please input your guesses",
+                 console::style(info.state.total_rounds + 1).green().bold());
+    }
+    if info.is_jsonl {
+        print_round_start_event(info);
+    }
+    let mut session = GameSession::new(info, word_to_guess);
+    let mut solver = round_solver(info);
+    while !session.is_over() {
+        let mut guess_word = String::new();
+        //stop the round when read EOF
+        let read = io::stdin().read_line(&mut guess_word)
+            .map_err(|source| Error::Io { path: "stdin".to_string(), source })?;
+        if read == 0 {
+            break;
+        }
+        guess_word.pop();
+        match session.submit_guess(&guess_word) {
+            Ok(outcome) if info.is_jsonl => print_event(guess_event(info, &session, &outcome, &mut solver)),
+            Err(error) if info.is_jsonl => print_event(invalid_event(&guess_word, &error)),
+            Ok(outcome) => {
+                print_guess(info, &session, &outcome, &mut solver);
+                print_alphabet(session.alphabet(), session.keyboard());
+                if outcome.is_correct {
+                    println!("CORRECT {}", session.history().len());
+                }
+            }
+            Err(error) => println!("{}", error)
+        }
+    }
+    info.record_session(&session);
+
+    if info.is_jsonl {
+        print_event(round_end_event(info, &session));
+    } else if !session.is_success() {
+        if is_tty {
+            println!(
+                "{} {}",
+                console::style("FAILED").red().bold(),
+                console::style(to_upper_word(session.answer())).green().italic()
+            );
+        } else {
+            println!("FAILED {}", to_upper_word(session.answer()));
+        }
+    }
+    Ok(())
+}
+
+/// Plays a round of several boards on stdin and stdout
+/// For each accepted guess, print a line of board number, colors and alphabet for every unsolved board,
+/// at last print the result of every board
+/// Return a result with Error
+fn guess_multi_round(info: &mut Info) -> Result<(), Error> {
+    let is_tty = atty::is(atty::Stream::Stdout) && !info.is_jsonl;
+    let mut answers: Vec<String> = vec![];
+    for _i in 0..info.boards {
+        if info.is_random {
+            answers.push(info.next_random_answer());
+        } else {
+            if is_tty {
+                println!("Default mode, please input the answer of board {}", answers.len() + 1);
+            }
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)
+                .map_err(|source| Error::Io { path: "stdin".to_string(), source })?;
+            answer.pop();
+            if !info.final_set.contains(&answer) {
+                return Err(Error::InvalidArgs(format!("'{}' is not in the final set", answer)));
+            }
+            answers.push(answer);
+        }
+    }
+    if is_tty {
+        println!("This is round {}, please input your guesses for {} boards",
+                 console::style(info.state.total_rounds + 1).green().bold(), info.boards);
+    }
+    if info.is_jsonl {
+        print_round_start_event(info);
+    }
+    let mut session = MultiSession::new(info, &answers);
+    let mut solvers: Vec<Option<Box<dyn Solver>>> = answers.iter().map(|_| round_solver(info)).collect();
+    while !session.is_over() {
+        let mut guess_word = String::new();
+        //stop the round when read EOF
+        let read = io::stdin().read_line(&mut guess_word)
+            .map_err(|source| Error::Io { path: "stdin".to_string(), source })?;
+        if read == 0 {
+            break;
+        }
+        guess_word.pop();
+        match session.submit_guess(&guess_word) {
+            Ok(outcomes) if info.is_jsonl => {
+                for (i, outcome) in outcomes.iter().enumerate() {
+                    if let Some(outcome) = outcome {
+                        let mut event = guess_event(info, &session.boards()[i], outcome, &mut solvers[i]);
+                        event["board"] = json!(i + 1);
+                        print_event(event);
+                    }
+                }
+            }
+            Err(error) if info.is_jsonl => print_event(invalid_event(&guess_word, &error)),
+            Ok(outcomes) => {
+                for (i, outcome) in outcomes.iter().enumerate() {
+                    if let Some(outcome) = outcome {
+                        print!("{} ", i + 1);
+                        print_colors(outcome);
+                        print_alphabet(session.boards()[i].alphabet(), session.boards()[i].keyboard());
+                    }
+                }
+            }
+            Err(error) => println!("{}", error)
+        }
+    }
+    info.record_multi_session(&session);
 
+    for (i, board) in session.boards().iter().enumerate() {
+        if info.is_jsonl {
+            let mut event = round_end_event(info, board);
+            event["board"] = json!(i + 1);
+            print_event(event);
+        } else if board.is_success() {
+            println!("CORRECT {} {}", i + 1, board.history().len());
+        } else if is_tty {
+            println!(
+                "{} {} {}",
+                console::style("FAILED").red().bold(),
+                i + 1,
+                console::style(to_upper_word(board.answer())).green().italic()
+            );
+        } else {
+            println!("FAILED {} {}", i + 1, to_upper_word(board.answer()));
+        }
+    }
+    Ok(())
+}
+
+/// Read lines of a guess and its colors like `crane RYGRR` given by another game,
+/// after each line print the number of possible answers left and the next guess of the solver
+/// Return a result with Error if stdin cannot be read
+fn solve(info: &Info) -> Result<(), Error> {
+    let is_tty = atty::is(atty::Stream::Stdout);
+    let table = info.pattern_table.as_deref();
+    let mut candidates = info.final_set.clone();
+    let mut solver = func::solver::new_solver(info);
+    let mut history: Vec<(String, Pattern)> = vec![];
+    if is_tty {
+        println!("Input your guess and its colors, like `crane RYGRR`");
+    }
+    loop {
+        let mut line = String::new();
+        //stop when read EOF
+        let read = io::stdin().read_line(&mut line)
+            .map_err(|source| Error::Io { path: "stdin".to_string(), source })?;
+        if read == 0 {
+            break;
+        }
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let colors: Option<Vec<Color>> = match parts[..] {
+            [guess, colors] if guess.chars().count() == info.word_length
+                && colors.chars().count() == info.word_length => colors.chars().map(|x| match x {
+                'G' | 'g' => Some(Color::G),
+                'Y' | 'y' => Some(Color::Y),
+                'R' | 'r' => Some(Color::R),
+                _ => None,
+            }).collect(),
+            _ => None,
+        };
+        let colors = match colors {
+            Some(colors) => colors,
+            None => {
+                println!("INVALID");
+                continue;
+            }
+        };
+        let guess = parts[0].to_lowercase();
+        let pattern = encode_colors(&colors);
+        candidates = func::solver::filter_candidates(&candidates, &guess, pattern, table);
+        history.push((guess, pattern));
+        let mut result = candidates.len().to_string();
+        if !candidates.is_empty() {
+            result += &format!(" {}", solver.next_guess(&history));
+        }
+        println!("{}", result);
+        if candidates.len() <= 1 {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Print the chosen games in state the same way as they were played
+fn replay(info: &Info) {
+    let is_tty = atty::is(atty::Stream::Stdout);
+    for n in info.replay_range() {
+        let game = &info.state.games[n];
+        if is_tty {
+            println!("This is game {}", console::style(n + 1).green().bold());
+        }
+        let alphabet = info.game_alphabet(game);
+        if game.answers.is_empty() {
+            let outcomes = func::replay_guesses(&game.answer, &game.guesses, info, &alphabet);
+            for (outcome, alphabet_color) in &outcomes {
+                print_colors(outcome);
+                print_alphabet(&alphabet, alphabet_color);
+            }
+            if game.is_success() {
+                println!("CORRECT {}", outcomes.len());
+            } else if is_tty {
+                println!("{} {}", console::style("FAILED").red().bold(), console::style(&game.answer).green().italic());
+            } else {
+                println!("FAILED {}", game.answer);
+            }
+            continue;
+        }
+        let boards: Vec<_> = game.answers.iter()
+            .map(|answer| func::replay_guesses(answer, &game.guesses, info, &alphabet))
+            .collect();
+        for turn in 0..game.guesses.len() {
+            for (i, outcomes) in boards.iter().enumerate() {
+                if let Some((outcome, alphabet_color)) = outcomes.get(turn) {
+                    print!("{} ", i + 1);
+                    print_colors(outcome);
+                    print_alphabet(&alphabet, alphabet_color);
+                }
+            }
+        }
+        for (i, outcomes) in boards.iter().enumerate() {
+            if outcomes.last().is_some_and(|x| x.0.is_correct) {
+                println!("CORRECT {} {}", i + 1, outcomes.len());
+            } else if is_tty {
+                println!("{} {} {}", console::style("FAILED").red().bold(), i + 1,
+                         console::style(&game.answers[i]).green().italic());
+            } else {
+                println!("FAILED {} {}", i + 1, game.answers[i]);
+            }
+        }
+    }
+}
+
+/// Print an event of `--protocol jsonl` in one line
+fn print_event(event: Value) {
+    println!("{}", event);
+}
+
+/// Event of the start of a round, numbered from 1 like the state file, with the guesses shared by every board
+fn print_round_start_event(info: &Info) {
+    print_event(json!({
+        "event": "round_start",
+        "round": info.state.total_rounds + 1,
+        "boards": info.boards,
+        "word_length": info.word_length,
+        "max_guesses": info.turn_budget(),
+    }));
+}
+
+/// Event of an accepted guess on a board, with its pattern, the keyboard after it and hints if required
+fn guess_event(info: &Info, session: &GameSession, outcome: &func::GuessOutcome, solver: &mut Option<Box<dyn Solver>>) -> Value {
+    let mut event = json!({
+        "event": "guess",
+        "guess": outcome.guess,
+        "pattern": func::color_vec_to_string(&outcome.colors),
+        "correct": outcome.is_correct,
+        "keyboard": func::keyboard_to_json(session.alphabet(), session.keyboard()),
+        "remaining_guesses": session.guesses_left(),
+    });
+    if info.is_hint {
+        event["hints"] = json!(session.hint_list());
+    }
+    if let Some(solver) = solver {
+        event["recommend"] = json!(recommend(solver.as_mut(), session));
+    }
+    event
+}
+
+/// Event of a rejected guess with the reason
+fn invalid_event(guess: &str, error: &Error) -> Value {
+    let reason = match error {
+        Error::InvalidWord(reason) => reason.to_string(),
+        error => error.to_string(),
+    };
+    json!({ "event": "invalid", "guess": guess, "reason": reason })
+}
+
+/// Event of the end of a round on a board, the round is the one just recorded
+fn round_end_event(info: &Info, session: &GameSession) -> Value {
+    json!({
+        "event": "round_end",
+        "round": info.state.total_rounds,
+        "won": session.is_success(),
+        "guesses": session.history().len(),
+        "answer": session.answer(),
+    })
+}
+
+/// Print the statistics of games in state as an event
+fn print_stats_event(info: &Info) {
+    let mut event = func::stats_to_json(info);
+    event["event"] = json!("stats");
+    print_event(event);
+}
+
+/// Print hints if required, and the recommended guess if there is a solver, then the colors of an accepted guess
+fn print_guess(info: &Info, session: &GameSession, outcome: &func::GuessOutcome, solver: &mut Option<Box<dyn Solver>>) {
+    if info.is_hint {
+        println!("total:{}\n{:?}", session.hint_list().len(), session.hint_list());
+    }
+    if let Some(solver) = solver {
+        println!("{}", recommend(solver.as_mut(), session));
+    }
+    print_colors(outcome);
+}
+
+/// The solver of a round of one board, only if recommendations are required with hints
+/// The solver keeps the possible answers between guesses of the round
+fn round_solver(info: &Info) -> Option<Box<dyn Solver>> {
+    if info.is_hint && info.is_recommend {
+        Some(func::solver::new_solver(info))
+    } else {
+        None
+    }
+}
+
+/// The next guess of the solver after the guesses of a board so far
+fn recommend(solver: &mut dyn Solver, session: &GameSession) -> String {
+    let history: Vec<(String, Pattern)> = session.history().iter()
+        .map(|(guess, colors)| (guess.clone(), encode_colors(colors)))
+        .collect();
+    solver.next_guess(&history)
+}
+
+/// Print the colors of a guess, colored letters if in tty
+fn print_colors(outcome: &func::GuessOutcome) {
+    let is_tty = atty::is(atty::Stream::Stdout);
+    for (letter, color) in outcome.guess.chars().zip(outcome.colors.iter()) {
+        if is_tty {
+            print!("{}",
+                   match color {
+                       Color::Y => console::style(letter).yellow(),
+                       Color::R => console::style(letter).black().bright(),
+                       Color::G => console::style(letter).green(),
+                       Color::X => console::style(letter).white()
+                   }
+            )
+        } else {
+            print!("{}", color.to_string());
+        }
+    }
+    print!(" ");
+}
+
+/// Receives the letters of keyboard and their colors and print
+/// If in tty, print letters, else just print color
+fn print_alphabet(alphabet: &Vec<char>, alphabet_color: &Vec<Color>) {
+    let is_tty = atty::is(atty::Stream::Stdout);
+    if is_tty {
+        for i in 0..alphabet.len() {
+            let letter = to_upper_word(&alphabet[i].to_string());
+            print!("{}",
+                   match alphabet_color[i] {
+                       Color::Y => console::style(letter).yellow().bold(),
+                       Color::R => console::style(letter).white().bold(),
+                       Color::G => console::style(letter).green().bold(),
+                       Color::X => console::style(letter).black().bright()
+                   }
+            )
+        }
+    } else {
+        for i in alphabet_color {
+            print!("{}", i.to_string());
+        }
+    }
+    println!();
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::OnceLock;
+use crate::{word_list_fingerprint, Color, Error, MAX_WORDLE_LENS};
+
+/// Color pattern of a guess encoded in base 3, the first letter is the highest digit
+/// R is 0, Y is 1 and G is 2
+pub type Pattern = u32;
+
+/// Longest words whose patterns fit in one byte, 3^5 = 243
+pub const MAX_TABLE_LENS: usize = 5;
+
+const CACHE_HEADER: &str = "wordle-patterns";
+
+/// Convert a vector of color to pattern
+pub fn encode_colors(colors: &Vec<Color>) -> Pattern {
+    let mut pattern: Pattern = 0;
+    for color in colors {
+        pattern = pattern * 3 + match color {
+            Color::G => 2,
+            Color::Y => 1,
+            Color::R | Color::X => 0,
+        };
+    }
+    pattern
+}
+
+/// Convert a pattern of a word of given length to a vector of color
+pub fn decode_pattern(mut pattern: Pattern, word_length: usize) -> Vec<Color> {
+    let mut colors = vec![Color::R; word_length];
+    for i in (0..word_length).rev() {
+        colors[i] = match pattern % 3 {
+            2 => Color::G,
+            1 => Color::Y,
+            _ => Color::R,
+        };
+        pattern /= 3;
+    }
+    colors
+}
+
+/// Number of letters that are G or Y in a pattern
+pub fn matched_letters(mut pattern: Pattern) -> usize {
+    let mut count = 0;
+    while pattern > 0 {
+        if !pattern.is_multiple_of(3) {
+            count += 1;
+        }
+        pattern /= 3;
+    }
+    count
+}
+
+/// The pattern of a correct guess, all letters are G
+pub fn correct_pattern(word_length: usize) -> Pattern {
+    (3 as Pattern).pow(word_length as u32) - 1
+}
+
+/// Receive two lowercase words, give the pattern of the guess with the same rules as calculate_color
+/// without building any vector
+pub fn get_pattern(answer: &str, guess: &str) -> Pattern {
+    if answer.is_ascii() && guess.is_ascii() {
+        pattern_of_letters(answer.as_bytes(), guess.as_bytes())
+    } else {
+        let answer: Vec<char> = answer.chars().collect();
+        let guess: Vec<char> = guess.chars().collect();
+        pattern_of_letters(&answer, &guess)
+    }
+}
+
+fn pattern_of_letters<T: PartialEq>(answer: &[T], guess: &[T]) -> Pattern {
+    let word_length = guess.len().min(MAX_WORDLE_LENS);
+    let mut digits = [0 as Pattern; MAX_WORDLE_LENS];
+    let mut used = [false; MAX_WORDLE_LENS];
+    for i in 0..word_length {
+        if guess[i] == answer[i] {
+            digits[i] = 2;
+            used[i] = true;
+        }
+    }
+    for i in 0..word_length {
+        if digits[i] == 2 {
+            continue;
+        }
+        //first unused letter in answer is marked, make sure G + Y <= actual num
+        for j in 0..word_length {
+            if !used[j] && guess[i] == answer[j] {
+                digits[i] = 1;
+                used[j] = true;
+                break;
+            }
+        }
+    }
+    let mut pattern: Pattern = 0;
+    for digit in &digits[..word_length] {
+        pattern = pattern * 3 + digit;
+    }
+    pattern
+}
+
+/// Patterns of every guess against every answer, one byte for each pair
+/// The cells are computed, or loaded from the cache file, when first used
+pub struct PatternTable {
+    guesses: Vec<String>,
+    answers: Vec<String>,
+    guess_index: HashMap<String, usize>,
+    answer_index: HashMap<String, usize>,
+    cache_path: String,
+    cells: OnceLock<Vec<u8>>,
+}
+
+impl PatternTable {
+    /// Receive words allowed to guess, possible answers, and the path of cache file (empty for no cache)
+    /// Return a result with Error if words are too long to fit the pattern in one byte
+    pub fn new(guesses: &[String], answers: &[String], cache_path: &str) -> Result<PatternTable, Error> {
+        if guesses.iter().chain(answers.iter()).any(|x| x.chars().count() > MAX_TABLE_LENS) {
+            return Err(Error::InvalidArgs(
+                format!("pattern table only supports words of at most {} letters", MAX_TABLE_LENS)));
+        }
+        Ok(PatternTable {
+            guess_index: guesses.iter().enumerate().map(|(i, x)| (x.clone(), i)).collect(),
+            answer_index: answers.iter().enumerate().map(|(i, x)| (x.clone(), i)).collect(),
+            guesses: guesses.to_vec(),
+            answers: answers.to_vec(),
+            cache_path: cache_path.to_string(),
+            cells: OnceLock::new(),
+        })
+    }
+
+    pub fn guess_id(&self, guess: &str) -> Option<usize> {
+        self.guess_index.get(guess).copied()
+    }
+
+    pub fn answer_id(&self, answer: &str) -> Option<usize> {
+        self.answer_index.get(answer).copied()
+    }
+
+    pub fn get_by_id(&self, guess_id: usize, answer_id: usize) -> Pattern {
+        self.cells()[guess_id * self.answers.len() + answer_id] as Pattern
+    }
+
+    /// Look up the pattern of two words, calculate it if one of them is not in the table
+    pub fn get(&self, guess: &str, answer: &str) -> Pattern {
+        match (self.guess_id(guess), self.answer_id(answer)) {
+            (Some(guess_id), Some(answer_id)) => self.get_by_id(guess_id, answer_id),
+            _ => get_pattern(answer, guess),
+        }
+    }
+
+    fn cells(&self) -> &Vec<u8> {
+        self.cells.get_or_init(|| {
+            if let Some(cells) = self.load_cache() {
+                return cells;
+            }
+            let mut cells: Vec<u8> = Vec::with_capacity(self.guesses.len() * self.answers.len());
+            for guess in &self.guesses {
+                for answer in &self.answers {
+                    cells.push(get_pattern(answer, guess) as u8);
+                }
+            }
+            self.save_cache(&cells);
+            cells
+        })
+    }
+
+    /// The cache file starts with a line of the sizes and a fingerprint of both lists
+    fn cache_header(&self) -> String {
+        let fingerprint = word_list_fingerprint(&self.guesses, &self.answers);
+        format!("{} {} {} {:016x}\n", CACHE_HEADER, self.guesses.len(), self.answers.len(), fingerprint)
+    }
+
+    fn load_cache(&self) -> Option<Vec<u8>> {
+        if self.cache_path.is_empty() {
+            return None;
+        }
+        let bytes = fs::read(&self.cache_path).ok()?;
+        let header = self.cache_header();
+        if !bytes.starts_with(header.as_bytes())
+            || bytes.len() != header.len() + self.guesses.len() * self.answers.len() {
+            return None;
+        }
+        Some(bytes[header.len()..].to_vec())
+    }
+
+    /// Failing to write the cache is not an error, the table is only rebuilt next time
+    fn save_cache(&self, cells: &[u8]) {
+        if self.cache_path.is_empty() {
+            return;
+        }
+        let mut bytes = self.cache_header().into_bytes();
+        bytes.extend_from_slice(cells);
+        let _ = fs::write(&self.cache_path, bytes);
+    }
+}
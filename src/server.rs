@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use crate::{color_vec_to_string, load_state, to_upper_word, Error, Game, GameSession, Info, State, Statistics};
+
+/// Longest name of a player, which is also the name of its state file
+const MAX_NAME_LENS: usize = 32;
+
+/// A connection that sends nothing for this long is closed, and its open round is lost
+const READ_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// State of each player by name, shared by every connection
+type Players = Arc<Mutex<HashMap<String, State>>>;
+
+/// Accept connections forever, each one is played in its own thread with its own session
+/// A player sends one command each line:
+/// `NAME alice` to log in, `NEW` to start a round, `GUESS word`, `STATS` and `QUIT`
+/// The server greets with `WORDLE length max_guesses`, and answers `HELLO name`, `ROUND n`,
+/// `STATS played wins current_streak max_streak`, `BYE`, `INVALID` or `ERROR message`
+/// A guess is answered like the command line, colors and alphabet, then `CORRECT n` or `FAILED WORD` at the end
+/// A round left with guesses by NEW, NAME, QUIT or a closed connection is saved as lost
+pub fn serve(listener: TcpListener, info: Arc<Info>) {
+    let players: Players = Arc::new(Mutex::new(HashMap::new()));
+    for stream in listener.incoming() {
+        //a failed connection does not stop the others
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        let info = info.clone();
+        let players = players.clone();
+        thread::spawn(move || {
+            let _ = handle_connection(stream, &info, &players);
+        });
+    }
+}
+
+/// Play with one connection until it quits, closes or is idle for too long
+fn handle_connection(stream: TcpStream, info: &Info, players: &Players) -> io::Result<()> {
+    //answers are short lines, send each one at once
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut name: Option<String> = None;
+    let mut session: Option<GameSession> = None;
+    let result = play(stream, info, players, &mut name, &mut session);
+    if let Some(name) = &name {
+        let _ = abandon(session.take(), info, players, name);
+    }
+    result
+}
+
+/// Answer each command of the connection, name and session are left for handle_connection when it ends
+fn play(stream: TcpStream, info: &Info, players: &Players,
+        name: &mut Option<String>, session: &mut Option<GameSession>) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    writeln!(writer, "WORDLE {} {}", info.word_length, info.max_guesses)?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let (command, argument) = match line.trim().split_once(' ') {
+            Some((command, argument)) => (command.to_ascii_uppercase(), argument.trim()),
+            None => (line.trim().to_ascii_uppercase(), ""),
+        };
+        let reply = match (command.as_str(), &*name) {
+            ("QUIT", _) => {
+                writeln!(writer, "BYE")?;
+                return Ok(());
+            }
+            ("NAME", old_name) => {
+                if !is_valid_name(argument) {
+                    format!("ERROR a name has 1 to {} letters, digits, - or _", MAX_NAME_LENS)
+                } else if let Err(err) = old_name.as_ref()
+                    .map_or(Ok(()), |old_name| abandon(session.take(), info, players, old_name)) {
+                    format!("ERROR {}", err)
+                } else {
+                    *name = Some(argument.to_string());
+                    format!("HELLO {}", argument)
+                }
+            }
+            ("NEW" | "GUESS" | "STATS", None) => "ERROR send NAME first".to_string(),
+            ("NEW", Some(name)) => match abandon(session.take(), info, players, name)
+                .and_then(|_| with_state(players, info, name, |state| state.total_rounds)) {
+                Ok(rounds) => {
+                    let day = (round_day(info, rounds) - 1) as usize;//cause do not exist day0
+                    *session = Some(GameSession::new(info, &info.final_set[info.answer_index(day)]));
+                    format!("ROUND {}", rounds + 1)
+                }
+                Err(err) => format!("ERROR {}", err),
+            },
+            ("GUESS", Some(name)) => match session.as_mut() {
+                None => "ERROR send NEW first".to_string(),
+                Some(round) => {
+                    let reply = guess(round, &argument.to_lowercase(), info, players, name);
+                    if round.is_over() {
+                        *session = None;
+                    }
+                    reply
+                }
+            },
+            ("STATS", Some(name)) => match with_state(players, info, name, |state| {
+                Statistics::from_games(&state.games, info.max_guesses)
+            }) {
+                Ok(stats) => format!("STATS {} {} {} {}",
+                                     stats.played, stats.wins, stats.current_streak, stats.max_streak),
+                Err(err) => format!("ERROR {}", err),
+            },
+            _ => format!("ERROR unknown command '{}'", command),
+        };
+        writer.write_all(format!("{}\n", reply).as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Apply a guess to the round, save the game of the player when the round is over
+fn guess(session: &mut GameSession, guess: &str, info: &Info, players: &Players, name: &str) -> String {
+    let outcome = match session.submit_guess(guess) {
+        Ok(outcome) => outcome,
+        Err(err) => return err.to_string(),
+    };
+    let mut reply = format!("{} {}", color_vec_to_string(&outcome.colors), color_vec_to_string(session.keyboard()));
+    if !session.is_over() {
+        return reply;
+    }
+    if session.is_success() {
+        reply += &format!("\nCORRECT {}", session.history().len());
+    } else {
+        reply += &format!("\nFAILED {}", to_upper_word(session.answer()));
+    }
+    if let Err(err) = record_game(players, info, name, session.to_game()) {
+        reply += &format!("\nERROR {}", err);
+    }
+    reply
+}
+
+/// Save a round that is not over as a lost game of the player, so leaving it does not keep the streak
+/// A round without guesses is dropped, the next round has the same answer
+fn abandon(session: Option<GameSession>, info: &Info, players: &Players, name: &str) -> Result<(), Error> {
+    match session {
+        Some(round) if !round.is_over() && !round.history().is_empty() => record_game(players, info, name, round.to_game()),
+        _ => Ok(()),
+    }
+}
+
+/// Answers of a player go on by the rounds played, like days of random mode
+fn round_day(info: &Info, rounds: i32) -> i32 {
+    info.day.max(1) + rounds
+}
+
+/// Names are used as file names, so only letters, digits, - and _ are allowed
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= MAX_NAME_LENS
+        && name.chars().all(|x| x.is_ascii_alphanumeric() || x == '-' || x == '_')
+}
+
+fn state_path(info: &Info, name: &str) -> String {
+    Path::new(&info.state_dir).join(format!("{}.json", name)).to_string_lossy().to_string()
+}
+
+/// Run f with the state of the player, which is loaded from its file the first time
+/// Return a result with Error if the file cannot be loaded
+fn with_state<T>(players: &Players, info: &Info, name: &str, f: impl FnOnce(&mut State) -> T) -> Result<T, Error> {
+    let mut players = players.lock().unwrap_or_else(|err| err.into_inner());
+    if !players.contains_key(name) {
+        players.insert(name.to_string(), load_state(&state_path(info, name))?);
+    }
+    Ok(f(players.get_mut(name).unwrap()))
+}
+
+/// Add a finished game to the state of the player and write its file
+/// The file is written while the states are locked, so games of one player from two connections keep their order
+fn record_game(players: &Players, info: &Info, name: &str, mut game: Game) -> Result<(), Error> {
+    let path = state_path(info, name);
+    with_state(players, info, name, |state| {
+        info.annotate_game(&mut game, round_day(info, state.total_rounds));
+        state.games.push(game);
+        state.total_rounds += 1;
+        let state_string = serde_json::to_string_pretty(state).unwrap();
+        fs::create_dir_all(&info.state_dir)
+            .and_then(|_| fs::write(&path, state_string))
+            .map_err(|source| Error::Io { path: path.clone(), source })
+    })?
+}
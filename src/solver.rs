@@ -0,0 +1,291 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use rand::prelude::{SliceRandom, StdRng};
+use rand::SeedableRng;
+use crate::{GameSession, Info, State};
+use crate::pattern::{encode_colors, get_pattern, Pattern, PatternTable};
+
+/// Name of the solver used without --solver
+pub const DEFAULT_SOLVER: &str = "entropy";
+
+/// Names of the built-in solvers
+pub const SOLVERS: &[&str] = &["entropy", "minimax", "frequency", "random"];
+
+/// A strategy that chooses guesses of a round
+pub trait Solver {
+    /// Receive every guess of this round so far with its pattern, return the next guess,
+    /// or an empty string if no answer fits the history
+    /// A history that does not go on from the one of the last call starts a new round
+    fn next_guess(&mut self, history: &[(String, Pattern)]) -> String;
+}
+
+/// Receive info, return the solver named by info.solver with the final set and pattern table of info
+pub fn new_solver(info: &Info) -> Box<dyn Solver> {
+    let candidates = Candidates::new(info);
+    match info.solver.as_str() {
+        "minimax" => Box::new(MinimaxSolver { candidates, opening: None }),
+        "frequency" => Box::new(FrequencySolver { candidates }),
+        "random" => Box::new(RandomSolver { candidates, rng: SeedableRng::seed_from_u64(info.seed) }),
+        //names are checked when analyzing args, entropy is the default
+        _ => Box::new(EntropySolver { candidates, opening: None }),
+    }
+}
+
+/// Answers still possible in a round, narrowed by each guess added to the history
+struct Candidates {
+    final_set: Vec<String>,
+    /// Words allowed to guess, shared with info
+    acceptable_set: Arc<Vec<String>>,
+    words: Vec<String>,
+    /// Guesses already applied to words
+    history: Vec<(String, Pattern)>,
+    table: Option<Arc<PatternTable>>,
+}
+
+impl Candidates {
+    fn new(info: &Info) -> Candidates {
+        Candidates {
+            final_set: info.final_set.clone(),
+            acceptable_set: info.acceptable_set.clone(),
+            words: info.final_set.clone(),
+            history: vec![],
+            table: info.pattern_table.clone(),
+        }
+    }
+
+    /// Apply the new guesses of history, start over if it does not go on from the history applied
+    fn update(&mut self, history: &[(String, Pattern)]) {
+        if !history.starts_with(&self.history) {
+            self.words = self.final_set.clone();
+            self.history.clear();
+        }
+        for (guess, pattern) in &history[self.history.len()..] {
+            self.words = filter_candidates(&self.words, guess, *pattern, self.table.as_deref());
+        }
+        self.history = history.to_vec();
+    }
+
+    /// The guess when at most two answers are possible, one of them, since no other guess can do better
+    fn last_guess(&self) -> Option<String> {
+        match self.words.len() {
+            0..=2 => Some(self.words.first().cloned().unwrap_or_default()),
+            _ => None,
+        }
+    }
+}
+
+/// Guess the acceptable word of the most information about the possible answers
+struct EntropySolver {
+    candidates: Candidates,
+    /// The first guess only depends on the word lists, so it is kept for later rounds
+    opening: Option<String>,
+}
+
+impl Solver for EntropySolver {
+    fn next_guess(&mut self, history: &[(String, Pattern)]) -> String {
+        if let (true, Some(opening)) = (history.is_empty(), &self.opening) {
+            return opening.clone();
+        }
+        self.candidates.update(history);
+        if let Some(guess) = self.candidates.last_guess() {
+            return guess;
+        }
+        let candidates = &self.candidates;
+        let guess = rank_guesses(&candidates.acceptable_set, &candidates.words, candidates.table.as_deref()).first()
+            .map(|x| x.word.clone()).unwrap_or_default();
+        if history.is_empty() {
+            self.opening = Some(guess.clone());
+        }
+        guess
+    }
+}
+
+/// Guess the acceptable word whose largest group of possible answers with the same pattern is the smallest
+struct MinimaxSolver {
+    candidates: Candidates,
+    /// The first guess only depends on the word lists, so it is kept for later rounds
+    opening: Option<String>,
+}
+
+impl Solver for MinimaxSolver {
+    fn next_guess(&mut self, history: &[(String, Pattern)]) -> String {
+        if let (true, Some(opening)) = (history.is_empty(), &self.opening) {
+            return opening.clone();
+        }
+        self.candidates.update(history);
+        if let Some(guess) = self.candidates.last_guess() {
+            return guess;
+        }
+        let words = &self.candidates.words;
+        let table = self.candidates.table.as_deref();
+        let possible: HashSet<&String> = words.iter().collect();
+        //a guess that may be the answer wins a tie, then the first word
+        let guess = self.candidates.acceptable_set.iter().min_by_key(|guess| {
+            let mut patterns = patterns_of(guess, words, table);
+            patterns.sort_unstable();
+            let largest = patterns.chunk_by(|a, b| a == b).map(|x| x.len()).max().unwrap_or(0);
+            (largest, !possible.contains(guess))
+        }).cloned().unwrap_or_default();
+        if history.is_empty() {
+            self.opening = Some(guess.clone());
+        }
+        guess
+    }
+}
+
+/// Guess the possible answer whose different letters appear in the most possible answers
+struct FrequencySolver {
+    candidates: Candidates,
+}
+
+impl Solver for FrequencySolver {
+    fn next_guess(&mut self, history: &[(String, Pattern)]) -> String {
+        self.candidates.update(history);
+        let words = &self.candidates.words;
+        let mut frequency: HashMap<char, usize> = HashMap::new();
+        for word in words {
+            for letter in distinct_letters(word) {
+                *frequency.entry(letter).or_insert(0) += 1;
+            }
+        }
+        //max_by_key keeps the last of equal words, so compare them in reverse order
+        words.iter().rev()
+            .max_by_key(|word| distinct_letters(word).iter().map(|x| frequency[x]).sum::<usize>())
+            .cloned().unwrap_or_default()
+    }
+}
+
+fn distinct_letters(word: &str) -> Vec<char> {
+    let mut letters: Vec<char> = word.chars().collect();
+    letters.sort_unstable();
+    letters.dedup();
+    letters
+}
+
+/// Guess a possible answer at random, seeded by info.seed
+struct RandomSolver {
+    candidates: Candidates,
+    rng: StdRng,
+}
+
+impl Solver for RandomSolver {
+    fn next_guess(&mut self, history: &[(String, Pattern)]) -> String {
+        self.candidates.update(history);
+        self.candidates.words.choose(&mut self.rng).cloned().unwrap_or_default()
+    }
+}
+
+/// Grade of a guess against a list of possible answers
+#[derive(Debug, Clone)]
+pub struct GuessScore {
+    pub word: String,
+    /// Expected information of the guess in bits
+    pub entropy: f64,
+    /// Expected number of possible answers left after the guess
+    pub expected_remaining: f64,
+}
+
+/// Receive a guess and the possible answers, group answers by the pattern of the guess
+/// and grade the guess by the sizes of groups
+pub fn score_guess(guess: &str, candidates: &[String], table: Option<&PatternTable>) -> GuessScore {
+    score_from_patterns(guess, patterns_of(guess, candidates, table))
+}
+
+/// The pattern of a guess against each possible answer
+fn patterns_of(guess: &str, candidates: &[String], table: Option<&PatternTable>) -> Vec<Pattern> {
+    match table {
+        Some(table) => candidates.iter().map(|answer| table.get(guess, answer)).collect(),
+        None => candidates.iter().map(|answer| get_pattern(answer, guess)).collect(),
+    }
+}
+
+/// Calculate entropy and expected remaining from the patterns of a guess against every possible answer
+pub fn score_from_patterns(guess: &str, mut patterns: Vec<Pattern>) -> GuessScore {
+    let total = patterns.len() as f64;
+    let mut entropy = 0.0;
+    let mut expected_remaining = 0.0;
+    patterns.sort_unstable();
+    for bucket in patterns.chunk_by(|a, b| a == b) {
+        let p = bucket.len() as f64 / total;
+        entropy -= p * p.log2();
+        expected_remaining += p * bucket.len() as f64;
+    }
+    GuessScore { word: guess.to_string(), entropy, expected_remaining }
+}
+
+/// Receive words allowed to guess and the possible answers, return the grades of guesses from best to worst
+/// Higher entropy is better, then fewer expected remaining, then a guess that may be the answer
+pub fn rank_guesses(guesses: &[String], candidates: &[String], table: Option<&PatternTable>) -> Vec<GuessScore> {
+    let mut scores: Vec<GuessScore> = match table {
+        Some(table) => rank_by_id(guesses, candidates, table),
+        None => guesses.iter().map(|guess| score_guess(guess, candidates, None)).collect(),
+    };
+    sort_scores(&mut scores, candidates);
+    scores
+}
+
+/// Look words up once, then grade with ids only
+fn rank_by_id(guesses: &[String], candidates: &[String], table: &PatternTable) -> Vec<GuessScore> {
+    let answer_ids: Option<Vec<usize>> = candidates.iter().map(|x| table.answer_id(x)).collect();
+    let answer_ids = match answer_ids {
+        Some(ids) => ids,
+        None => return guesses.iter().map(|guess| score_guess(guess, candidates, Some(table))).collect(),
+    };
+    guesses.iter().map(|guess| match table.guess_id(guess) {
+        Some(guess_id) => score_from_patterns(
+            guess, answer_ids.iter().map(|answer_id| table.get_by_id(guess_id, *answer_id)).collect()),
+        None => score_guess(guess, candidates, Some(table)),
+    }).collect()
+}
+
+/// Sort grades from best to worst
+fn sort_scores(scores: &mut [GuessScore], candidates: &[String]) {
+    let candidate_set: HashSet<&String> = candidates.iter().collect();
+    scores.sort_by(|a, b| {
+        b.entropy.partial_cmp(&a.entropy).unwrap_or(Ordering::Equal)
+            .then(a.expected_remaining.partial_cmp(&b.expected_remaining).unwrap_or(Ordering::Equal))
+            .then(candidate_set.contains(&b.word).cmp(&candidate_set.contains(&a.word)))
+            .then(a.word.cmp(&b.word))
+    });
+}
+
+/// Receive the possible answers, a guess and its pattern, return answers that give the same pattern
+pub fn filter_candidates(candidates: &[String], guess: &str, pattern: Pattern, table: Option<&PatternTable>) -> Vec<String> {
+    candidates.iter().filter(|answer| match table {
+        Some(table) => table.get(guess, answer) == pattern,
+        None => get_pattern(answer, guess) == pattern,
+    }).cloned().collect()
+}
+
+/// Play a round with the answer, start with the opening word if given, then always guess what the solver chooses
+pub fn play_with_solver(info: &Info, answer: &str, opening: Option<&String>, solver: &mut dyn Solver) -> GameSession {
+    let mut session = GameSession::new(info, answer);
+    let mut history: Vec<(String, Pattern)> = vec![];
+    let mut guess = match opening {
+        Some(opening) => opening.clone(),
+        None => solver.next_guess(&history),
+    };
+    while !session.is_over() {
+        let outcome = match session.submit_guess(&guess) {
+            Ok(outcome) => outcome,
+            Err(_) => break,
+        };
+        history.push((guess, encode_colors(&outcome.colors)));
+        guess = solver.next_guess(&history);
+    }
+    session
+}
+
+/// Play a round with every word in final set as the answer with the solver of info, return the games in a state
+/// Use the opening word in info, or the first guess of the solver
+pub fn bench(info: &Info) -> State {
+    let opening = if info.opening.is_empty() { None } else { Some(&info.opening) };
+    let mut solver = new_solver(info);
+    let mut state = State::new();
+    for answer in &info.final_set {
+        state.games.push(play_with_solver(info, answer, opening, solver.as_mut()).to_game());
+        state.total_rounds += 1;
+    }
+    state
+}
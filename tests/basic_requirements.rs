@@ -77,3 +77,166 @@ fn test_08_5_pts_config_file() {
     // override config in command line options
     TestCase::read("08_02_config_override").run_and_compare_result();
 }
+
+#[test]
+#[timeout(2000)]
+fn test_09_word_length() {
+    // 6-letter words from specified word lists
+    TestCase::read("09_01_word_length").run_and_compare_result();
+    // word lists do not match the specified length
+    TestCase::read("09_02_invalid_word_length").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_10_max_guesses() {
+    // a game that fails after 3 guesses
+    TestCase::read("10_01_max_guesses").run_and_compare_result();
+    // statistics of rounds played under different limits
+    TestCase::read("10_02_max_guesses_stats").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_11_bench() {
+    // run the recommender against every word in final set
+    TestCase::read("11_01_bench").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_12_strict_mode() {
+    // strict mode rejects yellow letters in the same position and absent letters
+    TestCase::read("12_01_strict_mode").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_13_adversarial_mode() {
+    // the answer is forced as late as possible and saved in state
+    TestCase::read("13_01_adversarial").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_14_multi_board() {
+    // two boards with shared guesses, saved with both answers
+    TestCase::read("14_01_multi_board").run_and_compare_game_state();
+    // hard modes of every board cannot be kept by one guess
+    TestCase::read("14_02_multi_board_strict").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_15_daily() {
+    // the answer is chosen by date and the date is saved in state
+    TestCase::read("15_01_daily").run_and_compare_game_state();
+    // a date already in state cannot be played again
+    TestCase::read("15_02_daily_played").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_16_share() {
+    // the emoji grid of a failed round
+    TestCase::read("16_01_share").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_17_replay() {
+    // a saved game of two boards is printed as it was played
+    TestCase::read("17_01_replay").run_and_compare_result();
+    // there is no second game in state
+    TestCase::read("17_02_replay_out_of_range").run_and_expect_exit();
+    // a game played with other lists is drawn with the letters of its own words
+    TestCase::read("17_03_replay_alphabet").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_18_command_line() {
+    // unknown flags, duplicate flags and bad values are errors
+    TestCase::read("18_01_unknown_flag").run_and_expect_exit();
+    TestCase::read("18_02_duplicate_flag").run_and_expect_exit();
+    TestCase::read("18_03_invalid_value").run_and_expect_exit();
+    // the solve subcommand narrows possible answers by given colors
+    TestCase::read("18_04_solve").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_19_toml_config() {
+    // settings from a TOML config and a flag, with where each value came from
+    TestCase::read("19_01_config_show").run_and_compare_result();
+    // a TOML config is used like a JSON one
+    TestCase::read("19_02_toml_config").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_20_word_lists() {
+    // a duplicate is a warning, the lists are still usable
+    TestCase::read("20_01_lists_check").run_and_compare_result();
+    // non-letters, wrong lengths and unacceptable answers are errors
+    TestCase::read("20_02_lists_check_errors").run_and_expect_exit();
+    // comments, blank lines, CRLF and capitals are normalized when loading
+    TestCase::read("20_03_normalized_list").run_and_compare_result();
+    // a list without any word cannot be played with
+    TestCase::read("20_04_empty_list").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_21_unicode_alphabet() {
+    // the keyboard has the letters of German lists, ß stays one letter in stats
+    TestCase::read("21_01_unicode_difficult").run_and_compare_result();
+    // hints work with a Russian list
+    TestCase::read("21_02_unicode_hint").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_22_language_packs() {
+    // German answers and keyboard with ß, ä, ö and ü in difficult mode
+    TestCase::read("22_01_lang_de").run_and_compare_result();
+    // the language can be set in a config file
+    TestCase::read("22_02_lang_config").run_and_compare_result();
+    TestCase::read("22_03_unknown_lang").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_23_weighted() {
+    // answers drawn by frequency are reproducible with a seed, and recorded as weighted
+    TestCase::read("23_01_weighted").run_and_compare_game_state();
+    // a frequency should be a positive number
+    TestCase::read("23_02_invalid_frequency").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_24_jsonl_protocol() {
+    // every event is a line of JSON, rejected guesses have a reason
+    TestCase::read("24_01_jsonl").run_and_compare_result();
+    // a guess event for each unsolved board, and a round end for each board
+    TestCase::read("24_02_jsonl_boards").run_and_compare_result();
+    // only text and jsonl are known
+    TestCase::read("24_03_unknown_protocol").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(3000)]
+fn test_25_solvers() {
+    // solve with the acceptable guess whose largest group of possible answers is the smallest
+    TestCase::read("25_01_solve_minimax").run_and_compare_result();
+    // only built-in solvers are known
+    TestCase::read("25_02_unknown_solver").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_26_hint() {
+    // hints are the acceptable words that fit every color so far
+    TestCase::read("26_01_hint").run_and_compare_result();
+}
@@ -100,6 +100,10 @@ impl TestCase {
     fn execute_program_and_feed_input(&self) -> Child {
         let mut command = Command::new(EXE_PATH.as_os_str())
             .args(&self.arguments)
+            // fixed clock for the timestamps saved in state
+            .env("SOURCE_DATE_EPOCH", "1660000000")
+            // no user config of whoever runs the tests
+            .env("XDG_CONFIG_HOME", "tests/no_such_dir")
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .spawn()
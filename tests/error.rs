@@ -0,0 +1,53 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use func::Error;
+
+mod helpers;
+
+use helpers::analyze;
+
+#[test]
+fn test_error_variants() {
+    assert!(matches!(analyze(&["-c", "tests/data/no_such_config.json"]),
+        Err(Error::Io { ref path, .. }) if path == "tests/data/no_such_config.json"));
+    assert!(matches!(analyze(&["--date", "2022-02-30"]), Err(Error::Parse { ref value, .. }) if value == "2022-02-30"));
+    assert!(matches!(analyze(&["-r", "-w", "cargo"]), Err(Error::InvalidArgs(_))));
+    assert!(matches!(analyze(&["-f", "tests/data/09_01_word_length_final.txt"]),
+        Err(Error::WordList { line: 1, .. })));
+}
+
+#[test]
+fn test_error_display_and_source() {
+    let err = analyze(&["-c", "tests/data/no_such_config.json"]).err().unwrap();
+    assert!(err.to_string().starts_with("tests/data/no_such_config.json: "));
+    assert!(std::error::Error::source(&err).is_some());
+    assert_eq!(Error::InvalidWord("not in the acceptable set").to_string(), "INVALID");
+}
+
+#[test]
+fn test_unreadable_stdin_is_io_error() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wordle"))
+        .args(["-w", "cigar"])
+        .env("XDG_CONFIG_HOME", "tests/no_such_dir")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // a guess that is not UTF-8
+    child.stdin.take().unwrap().write_all(b"\xff\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(74));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("error: stdin: "));
+}
+
+#[test]
+fn test_lists_check_errors_are_word_list_errors() {
+    let output = Command::new(env!("CARGO_BIN_EXE_wordle"))
+        .args(["lists", "check", "tests/data/20_lists_invalid_final.txt", "tests/data/20_lists_acceptable.txt", "-l", "6"])
+        .env("XDG_CONFIG_HOME", "tests/no_such_dir")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(65));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("error: word list tests/data/20_lists_invalid_final.txt line 2: "));
+}
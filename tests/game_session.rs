@@ -0,0 +1,46 @@
+use func::{Color, Error, GameSession, Info};
+
+#[test]
+fn test_session_success() {
+    let info = Info::new();
+    let mut session = GameSession::new(&info, "cargo");
+    assert!(matches!(session.submit_guess("crane"), Ok(ref outcome) if !outcome.is_correct));
+    assert!(matches!(session.submit_guess("abcde"), Err(Error::InvalidWord(_))));
+    let outcome = session.submit_guess("cargo").unwrap();
+    assert!(outcome.is_correct);
+    assert!(outcome.colors.iter().all(|x| matches!(x, Color::G)));
+    assert!(session.is_over());
+    assert!(session.is_success());
+    assert_eq!(session.history().len(), 2);
+    assert!(matches!(session.submit_guess("cargo"), Err(Error::GameOver)));
+}
+
+#[test]
+fn test_session_keyboard_and_failure() {
+    let mut info = Info::new();
+    info.max_guesses = 2;
+    let mut session = GameSession::new(&info, "cargo");
+    let outcome = session.submit_guess("crane").unwrap();
+    assert_eq!(func::color_vec_to_string(&outcome.colors), "GYYRR");
+    // C R A N E
+    assert!(matches!(session.keyboard()[2], Color::G));
+    assert!(matches!(session.keyboard()[17], Color::Y));
+    assert!(matches!(session.keyboard()[13], Color::R));
+    assert!(matches!(session.keyboard()[25], Color::X));
+    session.submit_guess("slate").unwrap();
+    assert!(session.is_over());
+    assert!(!session.is_success());
+    assert_eq!(session.to_game().guesses, vec!["CRANE", "SLATE"]);
+}
+
+#[test]
+fn test_adversarial_prefers_fewer_matched_letters() {
+    let mut info = Info::new();
+    info.is_adversarial = true;
+    // crane gives YYYRR against acrid and GRRRR against cloud, both groups have one answer
+    info.final_set = vec!["acrid".to_string(), "cloud".to_string()];
+    let mut session = GameSession::new(&info, "");
+    let outcome = session.submit_guess("crane").unwrap();
+    assert_eq!(func::color_vec_to_string(&outcome.colors), "GRRRR");
+    assert!(session.submit_guess("cloud").unwrap().is_correct);
+}
@@ -0,0 +1,17 @@
+// each test file uses some of the helpers
+#![allow(dead_code)]
+
+use func::{Error, Info};
+
+/// Owned words of a list
+pub fn to_list(words: &[&str]) -> Vec<String> {
+    words.iter().map(|x| x.to_string()).collect()
+}
+
+/// Analyze the command line `wordle ARGS` without any config file
+pub fn analyze(args: &[&str]) -> Result<Info, Error> {
+    let args = to_list(&[&["wordle"], args].concat());
+    let mut info = Info::new();
+    func::info_analyze(&mut String::new(), &mut info, &args, &[])?;
+    Ok(info)
+}
@@ -0,0 +1,90 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use serde_json::{json, Value};
+use func::Info;
+
+mod helpers;
+
+use helpers::analyze;
+
+/// Send a request and return the status and JSON body of the response
+fn request(port: u16, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    write!(stream, "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}", method, path, body.len(), body).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+/// The info of a server started with a seed
+fn server_info() -> Info {
+    analyze(&["serve", "--http", "-s", "3"]).unwrap()
+}
+
+#[test]
+fn test_http_games() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || func::http::serve(listener, Arc::new(server_info())));
+    // the answer of day 2 with the same options
+    let mut info = server_info();
+    info.apply_request(&json!({ "day": 2, "difficult": true })).unwrap();
+    let answer = info.next_random_answer();
+
+    let (status, game) = request(port, "POST", "/games", r#"{"day": 2, "difficult": true}"#);
+    assert_eq!(status, 201);
+    assert_eq!(game["id"], 1);
+    assert_eq!(game["status"], "playing");
+    assert_eq!(game["remaining_guesses"], 6);
+    assert_eq!(game["keyboard"]["a"], "X");
+    assert!(game.get("answer").is_none());
+
+    let (status, error) = request(port, "POST", "/games/1/guesses", r#"{"guess": "zzzzz"}"#);
+    assert_eq!((status, error), (422, json!({ "error": "INVALID", "reason": "not in the acceptable set" })));
+    let (status, guess) = request(port, "POST", "/games/1/guesses", &json!({ "guess": answer }).to_string());
+    assert_eq!(status, 200);
+    assert_eq!(guess["pattern"], "GGGGG");
+    assert_eq!(guess["correct"], true);
+    assert_eq!(guess["status"], "won");
+    assert_eq!(guess["remaining_guesses"], 5);
+    let first_letter = answer.chars().next().unwrap().to_string();
+    assert_eq!(guess["keyboard"][&first_letter], "G");
+
+    let (status, game) = request(port, "GET", "/games/1", "");
+    assert_eq!(status, 200);
+    assert_eq!(game["guesses"], json!([{ "guess": answer, "pattern": "GGGGG" }]));
+    assert_eq!(game["answer"], json!(answer));
+    assert_eq!(request(port, "POST", "/games/1/guesses", r#"{"guess": "cigar"}"#).0, 409);
+
+    // options that could read files of the server are refused
+    assert_eq!(request(port, "POST", "/games", r#"{"final_set": "/etc/passwd"}"#).0, 400);
+    assert_eq!(request(port, "POST", "/games", r#"{"seed": "x"}"#).0, 400);
+    assert_eq!(request(port, "GET", "/games/99", "").0, 404);
+    assert_eq!(request(port, "DELETE", "/games/1", "").0, 405);
+    assert_eq!(request(port, "GET", "/", "").0, 404);
+
+    // a head that never ends is cut off
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    write!(stream, "GET /games/1 HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "a".repeat(10000)).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 431 "));
+}
+
+#[test]
+fn test_request_options_over_server_info() {
+    let info = server_info();
+    // the seed on the command line of the server wins over the request
+    let mut game = info.clone();
+    game.apply_request(&json!({ "seed": 5, "max_guesses": 8 })).unwrap();
+    assert_eq!((game.seed, game.max_guesses), (3, 8));
+    assert!(info.clone().apply_request(&json!({ "max_guesses": 0 })).is_err());
+    // another language brings its lists and keyboard
+    let mut game = info.clone();
+    game.apply_request(&json!({ "lang": "de" })).unwrap();
+    assert_eq!(game.final_set.len(), func::lang::pack("de").unwrap().final_words.len());
+    assert!(game.keyboard[0].starts_with("qwertz"));
+}
@@ -0,0 +1,35 @@
+use func::{keyboard_of, lang};
+
+mod helpers;
+
+use helpers::analyze;
+
+#[test]
+fn test_language_packs_are_consistent() {
+    for name in lang::names() {
+        let pack = lang::pack(name).unwrap();
+        assert!(pack.acceptable_words.windows(2).all(|x| x[0] < x[1]), "{} acceptable words are not sorted", name);
+        for word in pack.final_words {
+            assert!(pack.acceptable_words.binary_search(word).is_ok(), "{} is not acceptable in {}", word, name);
+        }
+        for word in pack.acceptable_words {
+            assert_eq!(word.chars().count(), 5, "{} in {}", word, name);
+            assert!(word.chars().all(|x| pack.alphabet.contains(&x)), "{} in {}", word, name);
+        }
+        let keys: String = pack.keyboard.concat();
+        assert!(pack.alphabet.iter().all(|x| keys.contains(*x)), "keyboard of {} misses letters", name);
+    }
+    assert!(lang::pack("xx").is_none());
+}
+
+#[test]
+fn test_keyboard_of_lists_from_files() {
+    assert_eq!(keyboard_of(&['a', 'b', 'c', 'd', 'e', 'f', 'g']), vec!["abc", "def", "g"]);
+    // letters the english keyboard misses get a keyboard of the alphabet
+    let info = analyze(&["-f", "tests/data/21_ru_final.txt", "-a", "tests/data/21_ru_acceptable.txt"]).unwrap();
+    assert_eq!(info.keyboard.len(), 3);
+    assert_eq!(info.keyboard.concat().chars().collect::<Vec<char>>(), info.alphabet);
+    // english lists keep the keyboard of the pack
+    let info = analyze(&["-f", "tests/data/20_lists_final.txt", "-a", "tests/data/20_lists_acceptable.txt", "-l", "6"]).unwrap();
+    assert_eq!(info.keyboard, vec!["qwertyuiop", "asdfghjkl", "zxcvbnm"]);
+}
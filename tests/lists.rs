@@ -0,0 +1,50 @@
+use func::lists::parse_word_list;
+use func::{alphabet_of, to_upper_word, ALPHABET};
+
+#[test]
+fn test_parse_word_list() {
+    let list = parse_word_list("# comment\r\nCigar\r\n\r\n  rebut \r\ncigar\r\nab1de\r\nhumph!\r\n", 5);
+    assert_eq!(list.words, vec!["cigar", "rebut"]);
+    assert_eq!(list.lines["rebut"], 4);
+    let problems: Vec<(usize, bool)> = list.problems.iter().map(|x| (x.line, x.is_error)).collect();
+    assert_eq!(problems, vec![(5, false), (6, true), (7, true)]);
+    assert_eq!(list.problems[0].message, "'cigar' is a duplicate of line 2");
+}
+
+#[test]
+fn test_alphabet_of_lists() {
+    let english = vec!["cigar".to_string()];
+    assert_eq!(alphabet_of(&[&english]), ALPHABET.to_vec());
+    let spanish = vec!["niño".to_string(), "año".to_string()];
+    assert_eq!(alphabet_of(&[&spanish, &english]), vec!['a', 'c', 'g', 'i', 'n', 'o', 'r', 'ñ']);
+    assert_eq!(to_upper_word("straße"), "STRAßE");
+    assert_eq!(to_upper_word("слово"), "СЛОВО");
+}
+
+#[test]
+fn test_parse_word_list_frequency() {
+    let list = parse_word_list("rebut\t3\ncigar\t10\nhumph\nsissy\tmany\nawake 2 1\n", 5);
+    assert_eq!(list.words, vec!["cigar", "humph", "rebut"]);
+    assert_eq!(list.weights, vec![10, 1, 3]);
+    let lines: Vec<usize> = list.problems.iter().map(|x| x.line).collect();
+    assert_eq!(lines, vec![4, 5]);
+}
+
+#[test]
+fn test_parse_word_list_total_frequency() {
+    let list = parse_word_list("cigar\t18446744073709551615\nrebut\t1\n", 5);
+    assert_eq!(list.words, vec!["cigar"]);
+    assert_eq!(list.problems.len(), 1);
+    assert_eq!((list.problems[0].line, list.problems[0].is_error), (2, true));
+}
+
+#[test]
+fn test_parse_empty_word_list() {
+    for content in ["", "# nothing\n", "\n# nothing\n\n"] {
+        let list = parse_word_list(content, 5);
+        assert!(list.words.is_empty());
+        assert_eq!(list.problems.len(), 1);
+        assert!(list.problems[0].is_error);
+        assert_eq!(list.problems[0].message, "the list has no words");
+    }
+}
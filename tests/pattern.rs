@@ -0,0 +1,47 @@
+use func::pattern::{self, PatternTable};
+use func::{calculate_color, color_vec_to_string};
+
+mod helpers;
+
+use helpers::to_list;
+
+#[test]
+fn test_pattern_same_as_calculate_color() {
+    let words = to_list(&["cargo", "crane", "eerie", "geese", "llama", "label", "speed", "abbey", "kebab"]);
+    for answer in &words {
+        for guess in &words {
+            let colors = calculate_color(answer, guess);
+            let pattern = pattern::get_pattern(answer, guess);
+            assert_eq!(pattern, pattern::encode_colors(&colors), "{} {}", answer, guess);
+            assert_eq!(color_vec_to_string(&pattern::decode_pattern(pattern, 5)), color_vec_to_string(&colors));
+        }
+    }
+    assert_eq!(pattern::get_pattern("cargo", "cargo"), pattern::correct_pattern(5));
+    assert_eq!(pattern::matched_letters(pattern::get_pattern("acrid", "crane")), 3);
+    assert_eq!(pattern::matched_letters(pattern::correct_pattern(5)), 5);
+}
+
+#[test]
+fn test_pattern_table_cache() {
+    let guesses = to_list(&["crane", "slate", "cargo", "fuzzy"]);
+    let answers = to_list(&["cargo", "slate"]);
+    let cache_path = std::env::temp_dir().join(format!("wordle_pattern_cache_{}", std::process::id()));
+    let cache_path = cache_path.to_str().unwrap();
+    let _ = std::fs::remove_file(cache_path);
+    // build and save
+    let table = PatternTable::new(&guesses, &answers, cache_path).unwrap();
+    assert_eq!(table.get("crane", "cargo"), pattern::get_pattern("cargo", "crane"));
+    assert!(std::fs::metadata(cache_path).is_ok());
+    // load from cache
+    let table = PatternTable::new(&guesses, &answers, cache_path).unwrap();
+    for guess in &guesses {
+        for answer in &answers {
+            assert_eq!(table.get(guess, answer), pattern::get_pattern(answer, guess));
+        }
+    }
+    // word not in table
+    assert_eq!(table.get("abbey", "kebab"), pattern::get_pattern("kebab", "abbey"));
+    std::fs::remove_file(cache_path).unwrap();
+    // words too long for one byte
+    assert!(PatternTable::new(&to_list(&["planet"]), &to_list(&["planet"]), "").is_err());
+}
@@ -0,0 +1,84 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod helpers;
+
+use helpers::analyze;
+
+/// A connection to the server, sending a line and reading the lines of its answer
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(port: u16) -> Client {
+        let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        stream.set_nodelay(true).unwrap();
+        let mut client = Client { reader: BufReader::new(stream.try_clone().unwrap()), writer: stream };
+        assert_eq!(client.read(), "WORDLE 5 6");
+        client
+    }
+
+    fn read(&mut self) -> String {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    fn send(&mut self, line: &str) -> String {
+        self.writer.write_all(format!("{}\n", line).as_bytes()).unwrap();
+        self.read()
+    }
+}
+
+#[test]
+fn test_serve_players_over_tcp() {
+    let state_dir = std::env::temp_dir().join(format!("wordle-serve-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&state_dir);
+    let info = analyze(&["serve", "-s", "3", "--state-dir", state_dir.to_str().unwrap()]).unwrap();
+    let first_answer = info.final_set[info.answer_index(0)].clone();
+    let second_answer = info.final_set[info.answer_index(1)].clone();
+    let wrong_guess = if second_answer == "cigar" { "rebut" } else { "cigar" };
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let info = Arc::new(info);
+    std::thread::spawn(move || func::server::serve(listener, info));
+
+    let mut alice = Client::connect(port);
+    assert_eq!(alice.send("NEW"), "ERROR send NAME first");
+    assert!(alice.send("NAME ../alice").starts_with("ERROR"));
+    assert_eq!(alice.send("NAME alice"), "HELLO alice");
+    assert_eq!(alice.send("GUESS cigar"), "ERROR send NEW first");
+    assert_eq!(alice.send("NEW"), "ROUND 1");
+    assert_eq!(alice.send("GUESS zzzzz"), "INVALID");
+    assert!(alice.send(&format!("GUESS {}", first_answer)).starts_with("GGGGG "));
+    assert_eq!(alice.read(), "CORRECT 1");
+    assert_eq!(alice.send("STATS"), "STATS 1 1 1 1");
+
+    // another connection of the same player shares the state, another player has its own
+    let mut again = Client::connect(port);
+    assert_eq!(again.send("NAME alice"), "HELLO alice");
+    assert_eq!(again.send("NEW"), "ROUND 2");
+    // a round left with guesses is lost
+    assert_ne!(again.send(&format!("GUESS {}", wrong_guess)), "INVALID");
+    assert_eq!(again.send("NEW"), "ROUND 3");
+    assert_eq!(again.send("STATS"), "STATS 2 1 0 1");
+    let mut bob = Client::connect(port);
+    assert_eq!(bob.send("NAME bob"), "HELLO bob");
+    assert_eq!(bob.send("STATS"), "STATS 0 0 0 0");
+    assert_eq!(bob.send("QUIT"), "BYE");
+
+    let state = func::load_state(state_dir.join("alice.json").to_str().unwrap()).unwrap();
+    assert_eq!(state.games.len(), 2);
+    assert_eq!(state.games[0].answer, first_answer.to_uppercase());
+    assert_eq!(state.games[1].outcome, Some(func::Outcome::Loss));
+    // each round records the day its answer is drawn from
+    let days: Vec<_> = state.games.iter().map(|x| x.mode.as_ref().and_then(|mode| mode.day)).collect();
+    assert_eq!(days, vec![Some(1), Some(2)]);
+    assert!(!state_dir.join("bob.json").exists());
+    std::fs::remove_dir_all(&state_dir).unwrap();
+}
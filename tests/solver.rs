@@ -0,0 +1,66 @@
+use func::{Color, Info};
+use func::pattern::{encode_colors, get_pattern};
+use func::solver;
+
+mod helpers;
+
+use helpers::{analyze, to_list};
+
+#[test]
+fn test_score_guess() {
+    let candidates = to_list(&["carbo", "cargo", "carob", "carom"]);
+    // every answer gives a different pattern
+    let score = solver::score_guess("carob", &candidates, None);
+    assert!((score.entropy - 2.0).abs() < 1e-9);
+    assert!((score.expected_remaining - 1.0).abs() < 1e-9);
+    // every answer gives the same pattern
+    let score = solver::score_guess("fuzzy", &candidates, None);
+    assert_eq!(score.entropy, 0.0);
+    assert!((score.expected_remaining - 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_rank_guesses() {
+    let candidates = to_list(&["carbo", "cargo", "carob", "carom"]);
+    let guesses = to_list(&["fuzzy", "cargo", "carob"]);
+    let ranked = solver::rank_guesses(&guesses, &candidates, None);
+    assert_eq!(ranked.last().unwrap().word, "fuzzy");
+    assert_eq!(func::recommend_from_hint_list(&candidates, None).len(), 2);
+}
+
+fn solver_info(name: &str) -> Info {
+    analyze(&["bench", "--solver", name, "-l", "6",
+        "-f", "tests/data/09_01_word_length_final.txt", "-a", "tests/data/09_01_word_length_acceptable.txt"]).unwrap()
+}
+
+#[test]
+fn test_every_solver_solves_every_answer() {
+    for name in solver::SOLVERS {
+        let info = solver_info(name);
+        // one solver plays every round, each round starts over with an empty history
+        let mut player = solver::new_solver(&info);
+        for answer in &info.final_set {
+            let session = solver::play_with_solver(&info, answer, None, player.as_mut());
+            assert!(session.is_success(), "{} cannot solve {}", name, answer);
+        }
+    }
+}
+
+#[test]
+fn test_solver_guesses_with_history() {
+    let mut info = Info::new();
+    info.final_set = to_list(&["carbo", "cargo", "carob", "carom", "fuzzy"]);
+    let history = vec![("carob".to_string(), get_pattern("cargo", "carob"))];
+    let possible = solver::filter_candidates(&info.final_set, &history[0].0, history[0].1, None);
+    for name in solver::SOLVERS {
+        info.solver = name.to_string();
+        let mut player = solver::new_solver(&info);
+        assert!(info.acceptable_set.contains(&player.next_guess(&[])), "{}", name);
+        // the only possible answer is guessed
+        assert_eq!(possible.len(), 1);
+        assert_eq!(player.next_guess(&history), possible[0], "{}", name);
+        // no answer gives four green letters and a yellow one
+        let impossible = encode_colors(&vec![Color::G, Color::G, Color::G, Color::G, Color::Y]);
+        assert_eq!(player.next_guess(&[("cargo".to_string(), impossible)]), "", "{}", name);
+    }
+}
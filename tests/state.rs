@@ -0,0 +1,30 @@
+use func::{load_state, Error, Outcome, State, STATE_VERSION};
+
+#[test]
+fn test_state_migration_from_version_1() {
+    let mut state: State = serde_json::from_str(
+        r#"{"total_rounds": 2, "games": [
+            {"answer": "CARGO", "guesses": ["CRANE", "CARGO"]},
+            {"answer": "POSER", "guesses": ["CRANE"], "max_guesses": 1}
+        ]}"#).unwrap();
+    assert_eq!(state.version, 1);
+    state.migrate();
+    assert_eq!(state.version, STATE_VERSION);
+    assert_eq!(state.games[0].outcome, Some(Outcome::Win));
+    assert_eq!(state.games[1].outcome, Some(Outcome::Loss));
+    assert!(state.games[0].timestamp.is_none());
+    let saved = serde_json::to_value(&state).unwrap();
+    assert_eq!(saved["version"], STATE_VERSION);
+    assert_eq!(saved["games"][1]["outcome"], "loss");
+    assert!(saved["games"][0].get("mode").is_none());
+}
+
+#[test]
+fn test_load_state_errors() {
+    // a missing file is a new state
+    assert_eq!(load_state("tests/data/no_such_state.json").unwrap().total_rounds, 0);
+    // a file that cannot be read is not replaced by an empty state
+    assert!(matches!(load_state("tests/data"), Err(Error::Io { ref path, .. }) if path == "tests/data"));
+    // a newer version is kept as it is instead of losing what it added
+    assert!(matches!(load_state("tests/data/14_state_v9.json"), Err(Error::State { .. })));
+}
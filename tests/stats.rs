@@ -0,0 +1,34 @@
+use func::{Game, Statistics};
+
+fn game(answer: &str, guesses: &[&str]) -> Game {
+    serde_json::from_value(serde_json::json!({ "answer": answer, "guesses": guesses })).unwrap()
+}
+
+#[test]
+fn test_statistics_streaks_and_distribution() {
+    let games = vec![
+        game("CARGO", &["CRANE", "CARGO"]),
+        game("CARGO", &["CRANE", "SLATE", "CARGO"]),
+        game("POSER", &["CRANE", "SLATE", "POINT", "HELLO", "WORLD", "THERE"]),
+        game("HAPPY", &["HAPPY"]),
+        game("HIPPY", &["HAPPY", "HIPPY"]),
+    ];
+    let stats = Statistics::from_games(&games, 6);
+    assert_eq!(stats.played, 5);
+    assert_eq!(stats.wins, 4);
+    assert_eq!(stats.current_streak, 2);
+    assert_eq!(stats.max_streak, 2);
+    assert_eq!(stats.distribution, vec![1, 2, 1, 0, 0, 0]);
+    assert_eq!(stats.failures, 1);
+    assert_eq!(stats.win_percentage(), 80.0);
+    assert_eq!(stats.to_string().lines().next().unwrap(), "Played 5 Win% 80 Current streak 2 Max streak 2");
+    assert_eq!(stats.to_string().lines().nth(2).unwrap(), "2 ████████████████████ 2");
+    assert_eq!(stats.to_string().lines().last().unwrap(), "X ██████████ 1");
+}
+
+#[test]
+fn test_statistics_empty() {
+    let stats = Statistics::from_games(&vec![], 6);
+    assert_eq!(stats.win_percentage(), 0.0);
+    assert_eq!(stats.to_string().lines().count(), 8);
+}
@@ -0,0 +1,21 @@
+use func::Info;
+
+mod helpers;
+
+use helpers::analyze;
+
+#[test]
+fn test_weighted_answers_follow_frequency() {
+    let info = analyze(&["-r", "--weighted", "-s", "7", "-f", "tests/data/23_weighted_final.txt"]).unwrap();
+    let cigar = info.final_set.iter().position(|x| x == "cigar").unwrap();
+    let days = 2900;
+    let count = (0..days).filter(|day| info.answer_index(*day) == cigar).count();
+    // cigar has 20 of 29 in total
+    assert!(count > days * 6 / 10 && count < days * 8 / 10, "cigar chosen {} times", count);
+    // the same seed gives the same answers, another seed does not
+    let again = analyze(&["-r", "--weighted", "-s", "7", "-f", "tests/data/23_weighted_final.txt"]).unwrap();
+    let other = analyze(&["-r", "--weighted", "-s", "8", "-f", "tests/data/23_weighted_final.txt"]).unwrap();
+    let answers = |info: &Info| (0..100).map(|day| info.answer_index(day)).collect::<Vec<usize>>();
+    assert_eq!(answers(&info), answers(&again));
+    assert_ne!(answers(&info), answers(&other));
+}